@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::Write;
 use std::path::PathBuf;
 use std::{env, fs};
@@ -19,6 +20,26 @@ pub struct ModulesConfig {
     pub volume: bool,
     pub window_title: bool,
     pub workspaces: bool,
+    #[serde(default)]
+    pub taskbar: bool,
+    #[serde(default)]
+    pub submap: bool,
+    #[serde(default)]
+    pub keyboard_layout: bool,
+    #[serde(default)]
+    pub temperature: bool,
+    #[serde(default)]
+    pub disk: bool,
+    #[serde(default)]
+    pub fan: bool,
+    #[serde(default)]
+    pub pinned_apps: bool,
+    #[serde(default)]
+    pub power: bool,
+    #[serde(default)]
+    pub idle_inhibitor: bool,
+    #[serde(default)]
+    pub backlight: bool,
 }
 
 // For battery config
@@ -45,6 +66,548 @@ pub struct VolumeConfig {
     pub tooltip_format: String, // {device_name} (not added but will be added in future)
 }
 
+// For hyprland workspaces config
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct WorkspacesConfig {
+    pub all_outputs: bool,                     // show workspaces of every monitor on each bar
+    pub format: String,                        // eg: "{id}", "{name} {icons}", "{id} ({windows})"
+    pub persistent: Vec<i32>,                  // always shown even if empty eg: [1, 2, 3, 4, 5]
+    pub labels: HashMap<String, String>,       // replaces {name} for a workspace id eg: { "1" = "" }
+    pub window_icons: HashMap<String, String>, // window class to icon for {icons} eg: { firefox = "" }
+    pub default_window_icon: String,           // icon for classes not in window_icons, empty to skip
+    pub show_special: bool,                    // show special workspaces in a separate group
+    pub special_format: String,                // eg: "{name}", "{name} {windows}"
+    pub special_icon: String,                  // icon of the button which toggles the special group
+    pub scroll_wrap: bool,                     // scrolling past the last workspace goes to the first
+    pub scroll_skip_empty: bool,               // scrolling skips workspaces without windows
+}
+
+impl Default for WorkspacesConfig {
+    fn default() -> Self {
+        Self {
+            all_outputs: false,
+            format: "{id}".to_string(),
+            persistent: Vec::new(),
+            labels: HashMap::new(),
+            window_icons: HashMap::new(),
+            default_window_icon: String::new(),
+            show_special: true,
+            special_format: "{name}".to_string(),
+            special_icon: "\u{f069}".to_string(),
+            scroll_wrap: true,
+            scroll_skip_empty: false,
+        }
+    }
+}
+
+// For hyprland window title config
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct WindowTitleConfig {
+    pub format: String,              // eg: "{title}", "{class}: {title}", "{initial_title} ({pid})"
+    pub empty_format: String,        // shown when no window is focused eg: "Desktop"
+    pub max_length: u32,             // in characters, longer titles are ellipsized, 0 for no limit
+    pub rewrite: Vec<TitleRewrite>,  // applied in order to the formatted title
+    pub icon: bool,                  // show app icon from the .desktop file of the window class
+    pub icon_size: i32,
+    pub tooltip: bool,               // full title on hover
+}
+
+// Regex rewrite rule for window titles
+// eg: { pattern = " — Mozilla Firefox$", replace = "" }, replace can use $1 for groups
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TitleRewrite {
+    pub pattern: String,
+    pub replace: String,
+}
+
+impl Default for WindowTitleConfig {
+    fn default() -> Self {
+        Self {
+            format: "{title}".to_string(),
+            empty_format: "Desktop".to_string(),
+            max_length: 34,
+            rewrite: Vec::new(),
+            icon: false,
+            icon_size: 16,
+            tooltip: true,
+        }
+    }
+}
+
+// For hyprland taskbar config
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct TaskbarConfig {
+    pub all_outputs: bool,     // show windows of every monitor on each bar
+    pub show_title: bool,      // title next to the icon
+    pub max_title_length: u32, // in characters, 0 for no limit
+    pub icon_size: i32,
+    pub group_by_app: bool,    // one button per window class, click cycles its windows
+    pub tooltip: bool,         // titles of the windows on hover
+}
+
+impl Default for TaskbarConfig {
+    fn default() -> Self {
+        Self {
+            all_outputs: false,
+            show_title: false,
+            max_title_length: 20,
+            icon_size: 18,
+            group_by_app: false,
+            tooltip: true,
+        }
+    }
+}
+
+// For hyprland submap config
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct SubmapConfig {
+    pub format: String, // eg: "{name}", " {name}", hidden in the default submap
+}
+
+impl Default for SubmapConfig {
+    fn default() -> Self {
+        Self {
+            format: "{name}".to_string(),
+        }
+    }
+}
+
+// For hyprland keyboard layout config
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct KeyboardLayoutConfig {
+    pub format: String,                       // eg: "{short}", "{layout}", " {short}"
+    pub keyboard: String,                     // device name from `hyprctl devices`, empty for the main keyboard
+    pub short_names: HashMap<String, String>, // layout name to {short} eg: { "English (US)" = "us" }
+    pub tooltip: bool,                        // layouts of every keyboard on hover
+}
+
+impl Default for KeyboardLayoutConfig {
+    fn default() -> Self {
+        Self {
+            format: "{short}".to_string(),
+            keyboard: String::new(),
+            short_names: HashMap::new(),
+            tooltip: true,
+        }
+    }
+}
+
+// For cpu config
+// {usage}, {usageN} of cpu N, {freq_avg} current, {freq_min}, {freq_max} limits in GHz,
+// {load1}, {load5}, {load15}, {cores} one line per core
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct CpuConfig {
+    pub format: String,         // eg: "CPU: {usage}%", " {usage}% {freq_avg}GHz"
+    pub tooltip: bool,
+    pub tooltip_format: String, // eg: "Load: {load1} {load5} {load15}"
+    pub interval: u32,          // seconds between updates
+    pub medium_threshold: f64,  // usage from which the `medium` class is set
+    pub high_threshold: f64,    // usage from which the `high` class is set
+    pub display: String,        // "text", "bars" one bar per core, "graph" usage history
+    pub graph_length: usize,    // samples kept in the graph
+    pub graph_width: i32,       // in pixels
+}
+
+impl Default for CpuConfig {
+    fn default() -> Self {
+        Self {
+            format: "CPU: {usage}%".to_string(),
+            tooltip: true,
+            tooltip_format: "\
+<b>CPU</b>\n\
+Usage: {usage}%\n\
+Frequency: {freq_avg} GHz (max {freq_max} GHz)\n\
+Load: {load1} {load5} {load15}\n\
+{cores}"
+                .to_string(),
+            interval: 1,
+            medium_threshold: 30.0,
+            high_threshold: 70.0,
+            display: "text".to_string(),
+            graph_length: 30,
+            graph_width: 60,
+        }
+    }
+}
+
+// For memory config
+// {used}, {total}, {available}, {percentage}, {swap_used}, {swap_total}, {swap_percentage},
+// {zram_used}, {zram_original}, {zram_compressed}
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct MemoryConfig {
+    pub display: String,        // "used", "used_total", "percentage", "available", "available_percentage", "detailed", "custom"
+    pub format: String,         // used with display = "custom" eg: "RAM: {used} ({percentage}%)"
+    pub tooltip: bool,
+    pub tooltip_format: String,
+    pub interval: u32,          // seconds between updates
+    pub include_zfs_arc: bool,  // count ZFS ARC as used memory
+    pub medium_threshold: f64,  // usage from which the `medium` class is set
+    pub high_threshold: f64,    // usage from which the `high` class is set
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            display: "percentage".to_string(),
+            format: "RAM: {percentage}%".to_string(),
+            tooltip: true,
+            tooltip_format: "\
+<b>Memory</b>\n\
+Used: {used} / {total}\n\
+Available: {available}\n\
+Swap: {swap_used} / {swap_total}"
+                .to_string(),
+            interval: 2,
+            include_zfs_arc: false,
+            medium_threshold: 50.0,
+            high_threshold: 80.0,
+        }
+    }
+}
+
+// For the top processes popover of cpu and memory
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct TopProcessesConfig {
+    pub enabled: bool,    // open the popover when clicking cpu or memory
+    pub count: usize,     // processes listed
+    pub interval: u32,    // seconds between refreshes while open
+    pub allow_kill: bool, // show a button sending SIGTERM to the selected process
+}
+
+impl Default for TopProcessesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            count: 10,
+            interval: 2,
+            allow_kill: true,
+        }
+    }
+}
+
+// For temperature config
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct TemperatureConfig {
+    pub format: String,          // eg: "{icon} {temp}{unit}", "{label}: {temp}{unit}"
+    pub chip: String,            // hwmon name or thermal zone type eg: "k10temp", "coretemp", empty for first
+    pub label: String,           // sensor label of the chip eg: "Tctl", "Package id 0", empty for first
+    pub fahrenheit: bool,
+    pub critical_threshold: f64, // in °C, sets the `critical` class, 0 uses the sensor limit
+    pub interval: u32,           // seconds between updates
+    pub tooltip: bool,           // all sensors on hover
+}
+
+impl Default for TemperatureConfig {
+    fn default() -> Self {
+        Self {
+            format: "{icon} {temp}{unit}".to_string(),
+            chip: String::new(),
+            label: String::new(),
+            fahrenheit: false,
+            critical_threshold: 80.0,
+            interval: 2,
+            tooltip: true,
+        }
+    }
+}
+
+// For disk config
+// {mount}, {used}, {free}, {total}, {percentage}, {read}, {write} per second with io enabled
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct DiskConfig {
+    pub mounts: Vec<String>,              // eg: ["/", "/home"]
+    pub format: String,                   // eg: "{mount} {percentage}%", " {free}"
+    pub formats: HashMap<String, String>, // format for a mount point eg: { "/home" = " {free}" }
+    pub tooltip: bool,
+    pub tooltip_format: String,
+    pub warning_threshold: f64,           // usage from which the `warning` class is set
+    pub critical_threshold: f64,          // usage from which the `critical` class is set
+    pub io: bool,                         // read /proc/diskstats for {read} and {write}
+    pub interval: u32,                    // seconds between updates
+}
+
+impl Default for DiskConfig {
+    fn default() -> Self {
+        Self {
+            mounts: vec!["/".to_string()],
+            format: "{mount} {percentage}%".to_string(),
+            formats: HashMap::new(),
+            tooltip: true,
+            tooltip_format: "\
+<b>{mount}</b>\n\
+Used: {used} / {total}\n\
+Free: {free}"
+                .to_string(),
+            warning_threshold: 80.0,
+            critical_threshold: 95.0,
+            io: false,
+            interval: 30,
+        }
+    }
+}
+
+// For fan config
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct FanConfig {
+    pub format: String, // eg: "{icon} {rpm}", "{label} {pwm}%"
+    pub chip: String,   // hwmon name eg: "nct6775", "thinkpad", empty with no label shows the fastest fan
+    pub label: String,  // fan label of the chip eg: "fan1", "CPU Fan"
+    pub interval: u32,  // seconds between updates
+    pub tooltip: bool,  // all fans and temperatures on hover
+}
+
+impl Default for FanConfig {
+    fn default() -> Self {
+        Self {
+            format: "{icon} {rpm}".to_string(),
+            chip: String::new(),
+            label: String::new(),
+            interval: 2,
+            tooltip: true,
+        }
+    }
+}
+
+// For clock config
+// formats use strftime like specifiers in the user's locale eg: "%H:%M", "%a %d %b"
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct ClockConfig {
+    pub format: String,         // eg: "%I:%M", "%H:%M:%S"
+    pub format_alt: String,     // shown after right clicking the clock eg: "%a %d %b %H:%M"
+    pub tooltip: bool,
+    pub tooltip_format: String, // eg: "%A, %d %B %Y"
+    pub timezones: Vec<String>, // extra zones listed in the tooltip eg: ["Europe/London", "Asia/Tokyo"]
+    pub scroll_timezones: bool, // scrolling cycles the clock through the extra zones
+    pub calendar: bool,         // left click opens a month calendar
+    pub week_numbers: bool,
+    pub events_dir: String,     // .ics files shown in the calendar, subdirectories included eg: "~/.local/share/calendars"
+    pub upcoming_days: u32,     // days of upcoming events listed under today
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            format: "%I:%M".to_string(),
+            format_alt: "%a %d %b  %I:%M %p".to_string(),
+            tooltip: true,
+            tooltip_format: "<b>%A, %d %B %Y</b>".to_string(),
+            timezones: Vec::new(),
+            scroll_timezones: true,
+            calendar: true,
+            week_numbers: true,
+            events_dir: String::new(),
+            upcoming_days: 7,
+        }
+    }
+}
+
+// For timers shown in the clock, started from the clock popover or `better-bar msg timer`
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct TimerConfig {
+    pub format: String,               // shown next to the clock while running eg: "{icon} {remaining}", "{label} {remaining}"
+    pub presets: Vec<String>,         // buttons in the clock popover eg: ["5m", "25m", "1h30m"]
+    pub pomodoro_work: u32,           // minutes
+    pub pomodoro_short_break: u32,    // minutes
+    pub pomodoro_long_break: u32,     // minutes
+    pub pomodoro_rounds: u32,         // work rounds before a long break
+    pub notify: bool,
+    pub sound_command: String,        // run when a timer ends eg: "paplay /usr/share/sounds/freedesktop/stereo/complete.oga"
+}
+
+impl Default for TimerConfig {
+    fn default() -> Self {
+        Self {
+            format: "{icon} {remaining}".to_string(),
+            presets: vec!["5m".to_string(), "10m".to_string(), "25m".to_string()],
+            pomodoro_work: 25,
+            pomodoro_short_break: 5,
+            pomodoro_long_break: 15,
+            pomodoro_rounds: 4,
+            notify: true,
+            sound_command: String::new(),
+        }
+    }
+}
+
+// An alarm, from [[alarm.alarms]] in the config or added in the clock popover
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Alarm {
+    pub time: String,          // 24 hour local time eg: "07:30"
+    pub weekdays: Vec<String>, // empty for every day eg: ["mon", "tue", "wed", "thu", "fri"]
+    pub label: String,
+    pub enabled: bool,
+}
+
+impl Default for Alarm {
+    fn default() -> Self {
+        Self {
+            time: "07:00".to_string(),
+            weekdays: Vec::new(),
+            label: String::new(),
+            enabled: true,
+        }
+    }
+}
+
+// For alarms in the clock
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct AlarmConfig {
+    pub alarms: Vec<Alarm>,
+    pub notify: bool,
+    pub sound_command: String, // run when an alarm goes off eg: "paplay /usr/share/sounds/freedesktop/stereo/alarm-clock-elapsed.oga"
+    pub report_missed: bool,   // notify about alarms missed while suspended or while the bar was closed
+}
+
+impl Default for AlarmConfig {
+    fn default() -> Self {
+        Self {
+            alarms: Vec::new(),
+            notify: true,
+            sound_command: String::new(),
+            report_missed: true,
+        }
+    }
+}
+
+// For the launcher button
+// commands run with `sh -c`, empty ones do nothing
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct LauncherConfig {
+    pub icon: String,                 // glyph, icon name or image path eg: "\u{f427}", "view-app-grid-symbolic", "~/.face"
+    pub icon_size: i32,               // pixels, for icon names and images
+    pub menu: bool,                   // left click opens the built-in app menu instead of running `command`
+    pub command: String,              // left click eg: "better-launcher", "rofi -show drun"
+    pub right_command: String,
+    pub middle_command: String,
+    pub working_dir: String,          // empty for the bar's directory eg: "~"
+    pub env: HashMap<String, String>, // extra environment eg: { GTK_THEME = "Adwaita:dark" }
+    pub tooltip: String,
+}
+
+impl Default for LauncherConfig {
+    fn default() -> Self {
+        Self {
+            icon: "\u{f427}".to_string(),
+            icon_size: 18,
+            menu: false,
+            command: "better-launcher".to_string(),
+            right_command: String::new(),
+            middle_command: String::new(),
+            working_dir: String::new(),
+            env: HashMap::new(),
+            tooltip: "Applications".to_string(),
+        }
+    }
+}
+
+// For pinned apps config
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct PinnedAppsConfig {
+    pub apps: Vec<String>,   // desktop ids eg: ["firefox.desktop", "org.gnome.Nautilus", "kitty"]
+    pub icon_size: i32,
+    pub focus_running: bool, // click focuses an open window of the app instead of launching it, hyprland only
+    pub tooltip: bool,
+}
+
+impl Default for PinnedAppsConfig {
+    fn default() -> Self {
+        Self {
+            apps: Vec::new(),
+            icon_size: 20,
+            focus_running: true,
+            tooltip: true,
+        }
+    }
+}
+
+// For power menu config
+// actions: lock, logout, suspend, hibernate, reboot, shutdown
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct PowerConfig {
+    pub icon: String,
+    pub actions: Vec<String>,              // shown in this order
+    pub commands: HashMap<String, String>, // replaces the logind call of an action eg: { lock = "hyprlock", logout = "hyprctl dispatch exit" }
+    pub confirm: Vec<String>,              // actions asking before they run eg: ["logout", "reboot", "shutdown"]
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        let actions = ["lock", "logout", "suspend", "hibernate", "reboot", "shutdown"];
+        Self {
+            icon: "\u{f0425}".to_string(),
+            actions: actions.iter().map(|a| a.to_string()).collect(),
+            commands: HashMap::new(),
+            confirm: ["logout", "reboot", "shutdown"].iter().map(|a| a.to_string()).collect(),
+        }
+    }
+}
+
+// For idle inhibitor config
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct IdleInhibitorConfig {
+    pub activated_icon: String,
+    pub deactivated_icon: String,
+    pub timeout: u32,    // minutes after which the inhibitor turns itself off, 0 to keep it on
+    pub backend: String, // "auto" tries wayland then logind, or "wayland", "logind"
+    pub tooltip: bool,
+}
+
+impl Default for IdleInhibitorConfig {
+    fn default() -> Self {
+        Self {
+            activated_icon: "\u{f0208}".to_string(),
+            deactivated_icon: "\u{f0209}".to_string(),
+            timeout: 0,
+            backend: "auto".to_string(),
+            tooltip: true,
+        }
+    }
+}
+
+// For backlight config
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct BacklightConfig {
+    pub device: String,      // name in /sys/class/backlight eg: "intel_backlight", empty picks the one with the finest steps
+    pub format: String,      // eg: "{icon} {percentage}%"
+    pub icons: Vec<String>,  // from dim to bright, picked by level
+    pub step: u32,           // percent changed per scroll step
+    pub min_percentage: u32, // scrolling never goes below this so the screen stays visible
+    pub tooltip: bool,
+}
+
+impl Default for BacklightConfig {
+    fn default() -> Self {
+        Self {
+            device: String::new(),
+            format: "{icon} {percentage}%".to_string(),
+            icons: ["\u{f00de}", "\u{f00df}", "\u{f00e0}"].iter().map(|i| i.to_string()).collect(),
+            step: 5,
+            min_percentage: 1,
+            tooltip: true,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
     pub panel: PanelConfig,
@@ -52,6 +615,44 @@ pub struct Config {
     pub battery: BatteryConfig,
     pub network: NetworkConfig,
     pub volume: VolumeConfig,
+    #[serde(default)]
+    pub workspaces: WorkspacesConfig,
+    #[serde(default)]
+    pub window_title: WindowTitleConfig,
+    #[serde(default)]
+    pub taskbar: TaskbarConfig,
+    #[serde(default)]
+    pub submap: SubmapConfig,
+    #[serde(default)]
+    pub keyboard_layout: KeyboardLayoutConfig,
+    #[serde(default)]
+    pub cpu: CpuConfig,
+    #[serde(default)]
+    pub memory: MemoryConfig,
+    #[serde(default)]
+    pub top_processes: TopProcessesConfig,
+    #[serde(default)]
+    pub temperature: TemperatureConfig,
+    #[serde(default)]
+    pub disk: DiskConfig,
+    #[serde(default)]
+    pub fan: FanConfig,
+    #[serde(default)]
+    pub clock: ClockConfig,
+    #[serde(default)]
+    pub timer: TimerConfig,
+    #[serde(default)]
+    pub alarm: AlarmConfig,
+    #[serde(default)]
+    pub launcher: LauncherConfig,
+    #[serde(default)]
+    pub pinned_apps: PinnedAppsConfig,
+    #[serde(default)]
+    pub power: PowerConfig,
+    #[serde(default)]
+    pub idle_inhibitor: IdleInhibitorConfig,
+    #[serde(default)]
+    pub backlight: BacklightConfig,
 }
 
 impl Config {
@@ -69,6 +670,16 @@ impl Config {
                 volume: true,
                 window_title: false,
                 workspaces: false,
+                taskbar: false,
+                submap: false,
+                keyboard_layout: false,
+                temperature: false,
+                disk: false,
+                fan: false,
+                pinned_apps: false,
+                power: false,
+                idle_inhibitor: false,
+                backlight: false,
             },
             battery: BatteryConfig {
                 tooltip: true,
@@ -105,6 +716,25 @@ Level: {percentage}%\n\
 State: {state}"
                     .to_string(),
             },
+            workspaces: WorkspacesConfig::default(),
+            window_title: WindowTitleConfig::default(),
+            taskbar: TaskbarConfig::default(),
+            submap: SubmapConfig::default(),
+            keyboard_layout: KeyboardLayoutConfig::default(),
+            cpu: CpuConfig::default(),
+            memory: MemoryConfig::default(),
+            top_processes: TopProcessesConfig::default(),
+            temperature: TemperatureConfig::default(),
+            disk: DiskConfig::default(),
+            fan: FanConfig::default(),
+            clock: ClockConfig::default(),
+            timer: TimerConfig::default(),
+            alarm: AlarmConfig::default(),
+            launcher: LauncherConfig::default(),
+            pinned_apps: PinnedAppsConfig::default(),
+            power: PowerConfig::default(),
+            idle_inhibitor: IdleInhibitorConfig::default(),
+            backlight: BacklightConfig::default(),
         }
     }
 
@@ -1,6 +1,6 @@
 use gtk::prelude::*;
 use gtk::Application;
-use clap::{Parser, ArgAction};
+use clap::{Parser, Subcommand, ArgAction};
 
 mod ui;
 mod system;
@@ -19,12 +19,39 @@ struct Args {
     /// Show debug logs
     #[clap(short, long, action = ArgAction::SetTrue)]
     debug: bool,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Send a command to the running bar eg: `msg timer start 25m`
+    Msg {
+        #[clap(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Args
     let args = Args::parse();
+
+    if let Some(Command::Msg { args }) = args.command {
+        match system::ipc::send(&args) {
+            Ok(reply) => {
+                if !reply.is_empty() {
+                    println!("{}", reply);
+                }
+                return Ok(());
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
     
     // Logger
     Logger::set_logging_enabled(args.debug);
@@ -0,0 +1,162 @@
+// Hardware sensors from /sys/class/hwmon and /sys/class/thermal
+// Sensors are discovered once, modules only read the input files on update
+use once_cell::sync::Lazy;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("hwmon",LogLevel::Debug);
+}
+
+static SENSORS: Lazy<Vec<Sensor>> = Lazy::new(discover_sensors);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SensorKind {
+    Temperature, // in °C
+    Fan,         // in RPM
+    Pwm,         // fan duty in percent
+}
+
+#[derive(Debug, Clone)]
+pub struct Sensor {
+    pub kind: SensorKind,
+    pub chip: String,  // hwmon `name` eg: "k10temp", "coretemp", or thermal zone `type`
+    pub label: String, // eg: "Tctl", "Package id 0", file name when there is no label
+    pub input: PathBuf,
+    pub critical: Option<f64>, // from tempN_crit or the critical trip point
+}
+
+impl Sensor {
+    // Current value, None if the sensor went away
+    pub fn read(&self) -> Option<f64> {
+        let raw = read_number(&self.input)?;
+        Some(match self.kind {
+            SensorKind::Temperature => raw / 1000.0,
+            SensorKind::Fan => raw,
+            SensorKind::Pwm => raw / 255.0 * 100.0,
+        })
+    }
+}
+
+// All sensors found at startup
+pub fn sensors() -> &'static [Sensor] {
+    &SENSORS
+}
+
+// Sensor by chip and label, first of the kind when they are empty
+// names are stable across reboots unlike hwmonN and thermal_zoneN
+pub fn find_sensor(kind: SensorKind, chip: &str, label: &str) -> Option<&'static Sensor> {
+    sensors().iter().filter(|s| s.kind == kind).find(|s| {
+        (chip.is_empty() || s.chip.eq_ignore_ascii_case(chip))
+            && (label.is_empty() || s.label.eq_ignore_ascii_case(label))
+    })
+}
+
+fn read_number(path: &Path) -> Option<f64> {
+    fs::read_to_string(path).ok()?.trim().parse::<f64>().ok()
+}
+
+fn read_text(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+fn discover_sensors() -> Vec<Sensor> {
+    let mut sensors = Vec::new();
+    discover_hwmon(&mut sensors);
+    discover_thermal_zones(&mut sensors);
+    LOG.debug(&format!("Found {} sensors", sensors.len()));
+    sensors
+}
+
+fn sorted_entries(dir: &str) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries.flatten().map(|e| e.path()).collect(),
+        Err(e) => {
+            LOG.debug(&format!("Failed to read {}: {}", dir, e));
+            Vec::new()
+        }
+    };
+    entries.sort();
+    entries
+}
+
+// tempN_input, fanN_input and pwmN of every hwmon chip
+fn discover_hwmon(sensors: &mut Vec<Sensor>) {
+    for hwmon in sorted_entries("/sys/class/hwmon") {
+        let chip = read_text(&hwmon.join("name")).unwrap_or_else(|| {
+            hwmon.file_name().unwrap_or_default().to_string_lossy().into_owned()
+        });
+
+        discover_inputs(sensors, &hwmon, &chip, SensorKind::Temperature);
+        discover_inputs(sensors, &hwmon, &chip, SensorKind::Fan);
+        discover_inputs(sensors, &hwmon, &chip, SensorKind::Pwm);
+    }
+}
+
+// Inputs of one kind with their optional _label and _crit files
+fn discover_inputs(sensors: &mut Vec<Sensor>, hwmon: &Path, chip: &str, kind: SensorKind) {
+    let (prefix, suffix) = match kind {
+        SensorKind::Temperature => ("temp", "_input"),
+        SensorKind::Fan => ("fan", "_input"),
+        SensorKind::Pwm => ("pwm", ""),
+    };
+
+    let mut inputs: Vec<(u32, PathBuf)> = sorted_entries(&hwmon.to_string_lossy())
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let index = name.strip_prefix(prefix)?.strip_suffix(suffix)?.parse().ok()?;
+            Some((index, path))
+        })
+        .collect();
+    inputs.sort_by_key(|(index, _)| *index);
+
+    for (index, input) in inputs {
+        let name = format!("{}{}", prefix, index);
+        let critical = match kind {
+            SensorKind::Temperature => read_number(&hwmon.join(format!("{}_crit", name))).map(|c| c / 1000.0),
+            _ => None,
+        };
+        sensors.push(Sensor {
+            kind,
+            chip: chip.to_string(),
+            label: read_text(&hwmon.join(format!("{}_label", name))).unwrap_or(name),
+            input,
+            critical,
+        });
+    }
+}
+
+// thermal_zoneN/temp with the zone type as chip and label
+fn discover_thermal_zones(sensors: &mut Vec<Sensor>) {
+    for zone in sorted_entries("/sys/class/thermal") {
+        let is_zone = zone
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("thermal_zone"));
+        if !is_zone || !zone.join("temp").exists() {
+            continue;
+        }
+
+        let zone_type = read_text(&zone.join("type")).unwrap_or_default();
+        sensors.push(Sensor {
+            kind: SensorKind::Temperature,
+            chip: zone_type.clone(),
+            label: zone_type,
+            input: zone.join("temp"),
+            critical: critical_trip_point(&zone),
+        });
+    }
+}
+
+fn critical_trip_point(zone: &Path) -> Option<f64> {
+    (0..16).find_map(|i| {
+        let trip_type = read_text(&zone.join(format!("trip_point_{}_type", i)))?;
+        if trip_type != "critical" {
+            return None;
+        }
+        read_number(&zone.join(format!("trip_point_{}_temp", i))).map(|t| t / 1000.0)
+    })
+}
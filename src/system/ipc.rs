@@ -0,0 +1,134 @@
+// Commands sent to the running bar over a unix socket eg: `better-bar msg timer start 25m`
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("ipc",LogLevel::Debug);
+}
+
+// Gets the words after the command name, returns the reply or an error message
+pub type Handler = Box<dyn Fn(&[String]) -> Result<String, String>>;
+
+type Request = (Vec<String>, mpsc::Sender<Result<String, String>>);
+
+thread_local! {
+    static HANDLERS: RefCell<HashMap<String, Handler>> = RefCell::new(HashMap::new());
+    static STARTED: RefCell<bool> = const { RefCell::new(false) };
+}
+
+pub fn socket_path() -> PathBuf {
+    match env::var("XDG_RUNTIME_DIR") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir).join("better-bar.sock"),
+        _ => env::temp_dir().join(format!("better-bar-{}.sock", unsafe { libc::getuid() })),
+    }
+}
+
+// Handlers run on the gtk thread, registering a name again replaces it
+pub fn register(name: &str, handler: impl Fn(&[String]) -> Result<String, String> + 'static) {
+    HANDLERS.with(|handlers| handlers.borrow_mut().insert(name.to_string(), Box::new(handler)));
+}
+
+fn dispatch(args: &[String]) -> Result<String, String> {
+    let Some((name, rest)) = args.split_first() else {
+        return Err(format!("missing command, available: {}", command_names().join(", ")));
+    };
+    HANDLERS.with(|handlers| match handlers.borrow().get(name) {
+        Some(handler) => handler(rest),
+        None => Err(format!("unknown command '{}', available: {}", name, command_names().join(", "))),
+    })
+}
+
+fn command_names() -> Vec<String> {
+    let mut names: Vec<String> = HANDLERS.with(|handlers| handlers.borrow().keys().cloned().collect());
+    names.sort();
+    names
+}
+
+// Listen once per process, requests are handed to the gtk thread
+pub fn start_server() {
+    if STARTED.with(|started| started.replace(true)) {
+        return;
+    }
+
+    let path = socket_path();
+    if UnixStream::connect(&path).is_ok() {
+        LOG.error(&format!("Another bar is listening on {}, ipc disabled", path.display()));
+        return;
+    }
+    // left over from a bar that did not exit cleanly
+    let _ = std::fs::remove_file(&path);
+
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            LOG.error(&format!("Failed to bind {}: {}", path.display(), e));
+            return;
+        }
+    };
+    LOG.debug(&format!("Listening on {}", path.display()));
+
+    let (tx, rx) = async_channel::unbounded::<Request>();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if let Err(e) = handle_client(stream, &tx) {
+                LOG.error(&format!("Client error: {}", e));
+            }
+        }
+    });
+
+    glib::spawn_future_local(async move {
+        while let Ok((args, reply)) = rx.recv().await {
+            LOG.debug(&format!("Command: {:?}", args));
+            let _ = reply.send(dispatch(&args));
+        }
+    });
+}
+
+// One request per connection: a line of words, answered with "ok"/"error" and the message
+fn handle_client(stream: UnixStream, tx: &async_channel::Sender<Request>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+
+    let (reply_tx, reply_rx) = mpsc::channel();
+    let reply = match tx.send_blocking((args, reply_tx)) {
+        Ok(()) => reply_rx
+            .recv_timeout(Duration::from_secs(5))
+            .unwrap_or_else(|_| Err("bar did not answer".to_string())),
+        Err(_) => Err("bar is shutting down".to_string()),
+    };
+
+    let mut stream = stream;
+    match reply {
+        Ok(message) => writeln!(stream, "ok\n{}", message),
+        Err(message) => writeln!(stream, "error\n{}", message),
+    }
+}
+
+// Client side, used by `better-bar msg`
+pub fn send(args: &[String]) -> Result<String, String> {
+    let path = socket_path();
+    let mut stream =
+        UnixStream::connect(&path).map_err(|e| format!("bar is not running ({}): {}", path.display(), e))?;
+    writeln!(stream, "{}", args.join(" ")).map_err(|e| e.to_string())?;
+
+    let mut reader = BufReader::new(stream);
+    let mut status = String::new();
+    reader.read_line(&mut status).map_err(|e| e.to_string())?;
+    let message: String = reader.lines().map_while(Result::ok).collect::<Vec<_>>().join("\n");
+
+    match status.trim() {
+        "ok" => Ok(message),
+        _ => Err(message),
+    }
+}
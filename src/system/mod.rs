@@ -1,3 +1,5 @@
 pub mod updater;
 pub mod global;
-pub mod system_info_modules;
\ No newline at end of file
+pub mod system_info_modules;
+pub mod hwmon;
+pub mod ipc;
\ No newline at end of file
@@ -4,36 +4,52 @@
 use crate::config::config_helper;
 use crate::ui::modules::{
     cpu::cpu_widget::create_cpu_widget,
-    // memory::memory_widget::_create_memory_widget_percentage,
+    memory::memory_widget::create_memory_widget,
+    processes::{processes::attach_process_popover, processes_helper::ProcessSort},
 };
 use gtk::{Box as GtkBox, Label, prelude::*};
 
 pub struct SystemInfoModule;
 
+// Widgets created for the enabled modules
+pub struct SystemInfoWidgets {
+    pub cpu: Option<GtkBox>,
+    pub memory: Option<Label>,
+}
+
 impl SystemInfoModule {
     pub fn new() -> Self {
         Self
     }
 
-    pub fn create(&self, container: &GtkBox) -> Option<Label> {
+    pub fn create(&self, container: &GtkBox) -> SystemInfoWidgets {
         let config = config_helper::get_config().expect("Failed to get configuration");
 
-        // let memory_label = _create_memory_widget_percentage();
-        // memory_label.set_widget_name("memory");
-        // memory_label.add_css_class("modules");
-        // container.append(&memory_label);
+        let memory = if config.modules.memory {
+            let memory_label = create_memory_widget();
+            memory_label.set_widget_name("memory");
+            memory_label.add_css_class("modules");
+            container.append(&memory_label);
+            attach_process_popover(&memory_label, ProcessSort::Memory, config.top_processes.clone());
+            Some(memory_label)
+        } else {
+            None
+        };
 
-        let cpu_label = create_cpu_widget();
-        cpu_label.set_widget_name("cpu");
-        cpu_label.add_css_class("modules");
+        let cpu_widget = create_cpu_widget();
+        cpu_widget.set_widget_name("cpu");
+        cpu_widget.add_css_class("modules");
         if config.modules.cpu {
-            container.append(&cpu_label);
+            container.append(&cpu_widget);
+            attach_process_popover(&cpu_widget, ProcessSort::Cpu, config.top_processes.clone());
         }
 
-        if config.modules.cpu || config.modules.network {
-            Some(cpu_label)
+        let cpu = if config.modules.cpu || config.modules.network {
+            Some(cpu_widget)
         } else {
             None
-        }
+        };
+
+        SystemInfoWidgets { cpu, memory }
     }
 }
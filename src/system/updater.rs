@@ -6,7 +6,6 @@ use crate::ui::{
 };
 
 use crate::utils::logger::{LogLevel, Logger};
-use gtk::{glib};
 use lazy_static::lazy_static;
 
 lazy_static! {
@@ -28,11 +27,6 @@ impl SystemUpdater {
     }
 
     fn start_clock_updates(&self) {
-        let time_label = self.panel_state._time_label.clone();
-        glib::timeout_add_seconds_local(1, move || {
-            let now = chrono::Local::now();
-            time_label.set_text(&now.format("%I:%M").to_string());
-            glib::ControlFlow::Continue
-        });
+        self.panel_state._clock.start_updates();
     }
 }
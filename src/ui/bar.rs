@@ -1,4 +1,4 @@
-use gtk::{Application, ApplicationWindow};
+use gtk::{gdk, Application, ApplicationWindow};
 use gtk::{prelude::*};
 use gtk4_layer_shell::{KeyboardMode, Layer, LayerShell};
 
@@ -7,6 +7,7 @@ use crate::ui::modules::panel::PanelBuilder;
 use crate::ui::styles::load_css;
 use lazy_static::lazy_static;
 use crate::config::config_helper::{get_config};
+use crate::system::ipc;
 
 lazy_static! {
     static ref LOG: Logger = Logger::new("bar",LogLevel::Debug);
@@ -15,13 +16,43 @@ pub fn create_main_bar(app: &Application) {
     load_css();
     LOG.debug("Loaded css");
 
+    // Modules register their commands while the bars are built
+    ipc::start_server();
+
+    // One bar for each monitor
+    let monitors: Vec<gdk::Monitor> = match gdk::Display::default() {
+        Some(display) => {
+            let list = display.monitors();
+            (0..list.n_items())
+                .filter_map(|i| list.item(i).and_downcast::<gdk::Monitor>())
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    if monitors.is_empty() {
+        LOG.error("No monitors found, creating bar on the default output");
+        create_bar_window(app, None);
+        return;
+    }
+
+    for monitor in &monitors {
+        create_bar_window(app, Some(monitor));
+    }
+}
+
+fn create_bar_window(app: &Application, monitor: Option<&gdk::Monitor>) {
     let window = ApplicationWindow::builder().application(app).build();
 
-    setup_layer_shell(&window);
+    setup_layer_shell(&window, monitor);
     LOG.debug("Layer Shell setup complete");
 
+    // Connector name is the same as the hyprland monitor name eg: "DP-1"
+    let output = monitor.and_then(|m| m.connector()).map(|c| c.to_string());
+    LOG.debug(&format!("Creating bar for output: {:?}", output));
+
     let panel_builder = PanelBuilder::new();
-    let panel_state = panel_builder.build(&window);
+    let panel_state = panel_builder.build(&window, output);
     panel_state.start_updates();
     panel_state.refresh_workspaces();
     
@@ -33,11 +64,12 @@ pub fn create_main_bar(app: &Application) {
     window.present();
 }
 
-fn setup_layer_shell(window: &ApplicationWindow) {
+fn setup_layer_shell(window: &ApplicationWindow, monitor: Option<&gdk::Monitor>) {
     let config = get_config().unwrap();
     let edge = string_to_edge(&config.panel.position);
 
     LayerShell::init_layer_shell(window);
+    LayerShell::set_monitor(window, monitor);
     LayerShell::set_layer(window, Layer::Top);
     LayerShell::set_anchor(window, edge, true);
     LayerShell::set_anchor(window, gtk4_layer_shell::Edge::Left, true);
@@ -45,6 +77,14 @@ fn setup_layer_shell(window: &ApplicationWindow) {
     LayerShell::set_exclusive_zone(window, config.panel.height as i32);
     LayerShell::set_keyboard_mode(window, KeyboardMode::None);
 }
+// Popovers that take typing switch the bar to on-demand keyboard focus while open
+pub fn set_keyboard_focus(widget: &impl IsA<gtk::Widget>, focus: bool) {
+    let mode = if focus { KeyboardMode::OnDemand } else { KeyboardMode::None };
+    if let Some(window) = widget.root().and_downcast::<gtk::Window>() {
+        window.set_keyboard_mode(mode);
+    }
+}
+
 fn string_to_edge(s: &str) -> gtk4_layer_shell::Edge {
         match s.to_lowercase().as_str() {
             "top" => gtk4_layer_shell::Edge::Top,
@@ -0,0 +1,100 @@
+/* BACKLIGHT MODULE */
+
+use gtk::{glib, prelude::*, EventControllerScroll, EventControllerScrollFlags, Label};
+use std::rc::Rc;
+
+use crate::config::config::BacklightConfig;
+use crate::ui::modules::backlight::backlight_helper::{
+    find_device, format_backlight, percentage, set_brightness, step_brightness, watch_file, BacklightDevice,
+};
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("backlight",LogLevel::Debug);
+}
+
+pub struct Backlight {
+    label: Label,
+    config: BacklightConfig,
+}
+
+impl Backlight {
+    pub fn new(config: BacklightConfig) -> Self {
+        let label = Label::new(None);
+        label.set_widget_name("backlight");
+        label.add_css_class("modules");
+
+        Self { label, config }
+    }
+
+    pub fn widget(&self) -> &Label {
+        &self.label
+    }
+
+    pub fn start_updates(self) {
+        let Some(device) = find_device(&self.config.device) else {
+            LOG.error(&format!("No backlight device '{}'", self.config.device));
+            self.label.set_visible(false);
+            return;
+        };
+
+        let path = device.brightness_path();
+        let device = Rc::new(device);
+        let config = Rc::new(self.config);
+        let label = self.label;
+
+        let update = {
+            let label = label.clone();
+            let device = device.clone();
+            let config = config.clone();
+            move || {
+                let Some((brightness, max)) = device.read() else {
+                    label.set_text("N/A");
+                    return;
+                };
+                let percentage = percentage(brightness, max);
+                label.set_text(&format_backlight(&config, percentage));
+                if config.tooltip {
+                    label.set_tooltip_text(Some(&format!("{}: {}/{}", device.name, brightness, max)));
+                }
+            }
+        };
+        update();
+
+        // Scroll up brightens, down dims
+        let scroll = EventControllerScroll::new(EventControllerScrollFlags::VERTICAL | EventControllerScrollFlags::DISCRETE);
+        scroll.connect_scroll(move |_, _, dy| {
+            let steps = if dy < 0.0 { 1 } else { -1 };
+            adjust(&device, steps, &config);
+            glib::Propagation::Stop
+        });
+        label.add_controller(scroll);
+
+        // Every write to brightness, from here or from other tools, triggers an update
+        let (tx, rx) = async_channel::unbounded();
+        std::thread::spawn(move || watch_file(&path, tx));
+        glib::spawn_future_local(async move {
+            while rx.recv().await.is_ok() {
+                update();
+            }
+        });
+    }
+}
+
+fn adjust(device: &BacklightDevice, steps: i32, config: &BacklightConfig) {
+    let Some((brightness, max)) = device.read() else {
+        return;
+    };
+    let value = step_brightness(brightness, max, steps, config);
+    if value == brightness {
+        return;
+    }
+
+    let name = device.name.clone();
+    glib::spawn_future_local(async move {
+        if let Err(e) = set_brightness(&name, value).await {
+            LOG.error(&format!("Failed to set brightness of {}: {}", name, e));
+        }
+    });
+}
@@ -0,0 +1,143 @@
+use std::ffi::CString;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use zbus::Connection;
+
+use crate::config::config::BacklightConfig;
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("backlight",LogLevel::Debug);
+}
+
+const BACKLIGHT_DIR: &str = "/sys/class/backlight";
+
+#[derive(Debug, Clone)]
+pub struct BacklightDevice {
+    pub name: String, // eg: "intel_backlight"
+    pub path: PathBuf,
+}
+
+impl BacklightDevice {
+    pub fn brightness_path(&self) -> PathBuf {
+        self.path.join("brightness")
+    }
+
+    pub fn max_brightness(&self) -> Option<u32> {
+        read_value(&self.path.join("max_brightness"))
+    }
+
+    // (brightness, max brightness)
+    pub fn read(&self) -> Option<(u32, u32)> {
+        Some((read_value(&self.brightness_path())?, self.max_brightness()?))
+    }
+}
+
+fn read_value(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+// Configured device, or the one with the largest max_brightness
+pub fn find_device(name: &str) -> Option<BacklightDevice> {
+    let devices: Vec<BacklightDevice> = fs::read_dir(BACKLIGHT_DIR)
+        .ok()?
+        .flatten()
+        .map(|entry| BacklightDevice {
+            name: entry.file_name().to_string_lossy().to_string(),
+            path: entry.path(),
+        })
+        .collect();
+
+    if !name.is_empty() {
+        return devices.into_iter().find(|device| device.name == name);
+    }
+    devices.into_iter().max_by_key(|device| device.max_brightness().unwrap_or(0))
+}
+
+pub fn percentage(brightness: u32, max: u32) -> u32 {
+    if max == 0 {
+        return 0;
+    }
+    ((brightness as f64 / max as f64) * 100.0).round() as u32
+}
+
+// Raw value after moving `steps` config steps from `brightness`
+pub fn step_brightness(brightness: u32, max: u32, steps: i32, config: &BacklightConfig) -> u32 {
+    // lowest raw value still at or above min_percentage
+    let floor = ((config.min_percentage.min(100) as u64 * max as u64).div_ceil(100) as u32).min(max);
+    if steps < 0 && brightness <= floor {
+        return brightness;
+    }
+
+    let current = percentage(brightness, max) as i64;
+    let target = (current + steps as i64 * config.step.max(1) as i64).clamp(config.min_percentage.min(100) as i64, 100);
+    // at least one raw step so devices with few levels still move
+    let raw = ((target as f64 / 100.0) * max as f64).round() as u32;
+    let raw = match steps.signum() {
+        1 if raw <= brightness => brightness + 1,
+        -1 if raw >= brightness => brightness.saturating_sub(1),
+        _ => raw,
+    };
+    raw.clamp(floor, max)
+}
+
+fn get_icon(percentage: u32, icons: &[String]) -> &str {
+    if icons.is_empty() {
+        return "";
+    }
+    let index = (percentage as usize * icons.len() / 101).min(icons.len() - 1);
+    &icons[index]
+}
+
+pub fn format_backlight(config: &BacklightConfig, percentage: u32) -> String {
+    config
+        .format
+        .replace("{icon}", get_icon(percentage, &config.icons))
+        .replace("{percentage}", &percentage.to_string())
+}
+
+// logind writes the value for the caller's session, no root or udev rule needed
+pub async fn set_brightness(device: &str, value: u32) -> zbus::Result<()> {
+    let connection = Connection::system().await?;
+    connection
+        .call_method(
+            Some("org.freedesktop.login1"),
+            "/org/freedesktop/login1/session/auto",
+            Some("org.freedesktop.login1.Session"),
+            "SetBrightness",
+            &("backlight", device, value),
+        )
+        .await?;
+    Ok(())
+}
+
+// Blocks, sending an event each time the file is written
+pub fn watch_file(path: &Path, tx: async_channel::Sender<()>) {
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return;
+    };
+
+    unsafe {
+        let fd = libc::inotify_init1(libc::IN_CLOEXEC);
+        if fd < 0 {
+            LOG.error("Failed to init inotify");
+            return;
+        }
+        if libc::inotify_add_watch(fd, c_path.as_ptr(), libc::IN_MODIFY) < 0 {
+            LOG.error(&format!("Failed to watch {}", path.display()));
+            libc::close(fd);
+            return;
+        }
+
+        let mut buffer = [0u8; 4096];
+        loop {
+            let read = libc::read(fd, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len());
+            if read <= 0 || tx.send_blocking(()).is_err() {
+                break;
+            }
+        }
+        libc::close(fd);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod backlight;
+pub mod backlight_helper;
\ No newline at end of file
@@ -0,0 +1,419 @@
+// Alarms shared by the clocks of every bar, added in the config, the popover or over ipc
+use gtk::{glib, prelude::*};
+use gtk::{Align, Box as GtkBox, Button, Entry, Label, Orientation, Switch, ToggleButton};
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use crate::config::config::{Alarm, AlarmConfig};
+use crate::system::ipc;
+use crate::ui::modules::clock::alarm_helper::{
+    all_weekdays, describe_weekdays, format_timestamp, next_occurrence, occurrences, parse_weekdays, title,
+    validate, weekday_name, AlarmState, ALARMS_STATE_FILE,
+};
+use crate::ui::modules::clock::clock_helper::next_tick_delay;
+use crate::ui::modules::clock::timer::now;
+use crate::utils::notify::{run_command, send_notification};
+use crate::utils::state::{load_state, save_state};
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("alarm",LogLevel::Debug);
+}
+
+// Checks run each minute, a longer gap means the system was suspended
+const MAX_CHECK_GAP: i64 = 120;
+
+thread_local! {
+    static SERVICE: RefCell<Option<Rc<AlarmService>>> = const { RefCell::new(None) };
+}
+
+pub struct AlarmService {
+    config: AlarmConfig,
+    state: RefCell<AlarmState>,
+    listeners: RefCell<Vec<Box<dyn Fn() -> bool>>>,
+}
+
+impl AlarmService {
+    // The first clock creates the service, later ones share it
+    pub fn get(config: &AlarmConfig) -> Rc<AlarmService> {
+        SERVICE.with(|service| {
+            service
+                .borrow_mut()
+                .get_or_insert_with(|| {
+                    for alarm in &config.alarms {
+                        if let Err(e) = validate(alarm) {
+                            LOG.error(&format!("Alarm '{}' in config: {}", title(alarm), e));
+                        }
+                    }
+                    let service = Rc::new(AlarmService {
+                        config: config.clone(),
+                        state: RefCell::new(load_state(ALARMS_STATE_FILE)),
+                        listeners: RefCell::new(Vec::new()),
+                    });
+                    service.register_ipc();
+                    service.check();
+                    service.schedule_check();
+                    service
+                })
+                .clone()
+        })
+    }
+
+    // Called after every change, the listener is dropped once it returns false
+    pub fn subscribe(&self, listener: impl Fn() -> bool + 'static) {
+        self.listeners.borrow_mut().push(Box::new(listener));
+    }
+
+    pub fn is_ringing(&self) -> bool {
+        !self.state.borrow().ringing.is_empty()
+    }
+
+    pub fn ringing(&self) -> Vec<String> {
+        self.state.borrow().ringing.clone()
+    }
+
+    // Config alarms first, then the added ones, with whether they can be edited
+    pub fn alarms(&self) -> Vec<(Alarm, bool)> {
+        let config = self.config.alarms.iter().map(|alarm| (alarm.clone(), false));
+        let added = self.state.borrow().alarms.clone().into_iter().map(|alarm| (alarm, true));
+        config.chain(added).collect()
+    }
+
+    fn changed(&self) {
+        self.save();
+        self.listeners.borrow_mut().retain(|listener| listener());
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_state(ALARMS_STATE_FILE, &*self.state.borrow()) {
+            LOG.error(&format!("Failed to save alarms: {}", e));
+        }
+    }
+
+    pub fn add(&self, alarm: Alarm) -> Result<(), String> {
+        validate(&alarm)?;
+        self.state.borrow_mut().alarms.push(alarm);
+        self.changed();
+        Ok(())
+    }
+
+    // index into the added alarms
+    pub fn remove(&self, index: usize) -> Result<(), String> {
+        {
+            let mut state = self.state.borrow_mut();
+            if index >= state.alarms.len() {
+                return Err(format!("no added alarm {}", index));
+            }
+            state.alarms.remove(index);
+        }
+        self.changed();
+        Ok(())
+    }
+
+    pub fn set_enabled(&self, index: usize, enabled: bool) {
+        if let Some(alarm) = self.state.borrow_mut().alarms.get_mut(index) {
+            alarm.enabled = enabled;
+        }
+        self.changed();
+    }
+
+    pub fn dismiss(&self) {
+        self.state.borrow_mut().ringing.clear();
+        self.changed();
+    }
+
+    fn schedule_check(self: &Rc<Self>) {
+        let weak: Weak<Self> = Rc::downgrade(self);
+        glib::timeout_add_local_once(next_tick_delay(false), move || {
+            if let Some(service) = weak.upgrade() {
+                service.check();
+                service.schedule_check();
+            }
+        });
+    }
+
+    fn check(&self) {
+        let now = now();
+        let last_checked = self.state.borrow().last_checked;
+        // first start, nothing can have been missed
+        if last_checked == 0 {
+            self.state.borrow_mut().last_checked = now;
+            self.save();
+            return;
+        }
+
+        let mut due: Vec<(i64, Alarm)> = self
+            .alarms()
+            .into_iter()
+            .flat_map(|(alarm, _)| {
+                occurrences(&alarm, last_checked, now).into_iter().map(move |at| (at, alarm.clone()))
+            })
+            .collect();
+        due.sort_by_key(|(at, _)| *at);
+
+        // after a suspend or a restart only the current minute rings, the rest is reported
+        let suspended = now - last_checked > MAX_CHECK_GAP;
+        let (missed, ring): (Vec<_>, Vec<_>) = due.into_iter().partition(|(at, _)| suspended && now - at >= 60);
+
+        self.state.borrow_mut().last_checked = now;
+        for (_, alarm) in &ring {
+            self.ring(alarm);
+        }
+        if !missed.is_empty() {
+            self.report_missed(&missed);
+        }
+        // only last_checked moved, nothing to redraw
+        if ring.is_empty() && missed.is_empty() {
+            self.save();
+        } else {
+            self.changed();
+        }
+    }
+
+    fn ring(&self, alarm: &Alarm) {
+        LOG.debug(&format!("Alarm {} went off", title(alarm)));
+        self.state.borrow_mut().ringing.push(title(alarm));
+        if self.config.notify {
+            send_notification("alarm", "Alarm", &title(alarm));
+        }
+        run_command(&self.config.sound_command);
+    }
+
+    fn report_missed(&self, missed: &[(i64, Alarm)]) {
+        LOG.debug(&format!("{} alarms missed", missed.len()));
+        if !self.config.report_missed {
+            return;
+        }
+        let body = missed
+            .iter()
+            .map(|(at, alarm)| format!("{}  {}", format_timestamp(*at), title(alarm)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        send_notification("alarm-missed", "Missed alarms", &body);
+    }
+
+    fn register_ipc(self: &Rc<Self>) {
+        let weak = Rc::downgrade(self);
+        ipc::register("alarm", move |args| match weak.upgrade() {
+            Some(service) => service.handle_command(args),
+            None => Err("alarms are not available".to_string()),
+        });
+    }
+
+    // alarm list | add <HH:MM> [mon,tue|weekdays|weekend] [label] | remove <n> | dismiss
+    fn handle_command(&self, args: &[String]) -> Result<String, String> {
+        let command = args.first().map(String::as_str).unwrap_or("list");
+        let rest = args.get(1..).unwrap_or_default();
+
+        match command {
+            "list" => Ok(self.list()),
+            "add" => {
+                let time = rest.first().ok_or("usage: alarm add <HH:MM> [weekdays] [label]")?;
+                let mut words = rest[1..].to_vec();
+                let days: Vec<String> = match words.first() {
+                    Some(first) => first.split(',').map(String::from).collect(),
+                    None => Vec::new(),
+                };
+                let weekdays = if !days.is_empty() && parse_weekdays(&days).is_ok() {
+                    words.remove(0);
+                    days
+                } else {
+                    Vec::new()
+                };
+                self.add(Alarm {
+                    time: time.clone(),
+                    weekdays,
+                    label: words.join(" "),
+                    enabled: true,
+                })?;
+                Ok(String::new())
+            }
+            "remove" => {
+                let index = rest
+                    .first()
+                    .and_then(|n| n.parse().ok())
+                    .ok_or("usage: alarm remove <n>, see alarm list")?;
+                self.remove(index)?;
+                Ok(String::new())
+            }
+            "dismiss" => {
+                self.dismiss();
+                Ok(String::new())
+            }
+            _ => Err(format!("unknown alarm command '{}'", command)),
+        }
+    }
+
+    // added alarms are numbered for `alarm remove`
+    fn list(&self) -> String {
+        let now = now();
+        let mut index = 0;
+        self.alarms()
+            .iter()
+            .map(|(alarm, editable)| {
+                let id = if *editable {
+                    index += 1;
+                    (index - 1).to_string()
+                } else {
+                    "config".to_string()
+                };
+                let next = match next_occurrence(alarm, now) {
+                    Some(at) => format_timestamp(at),
+                    None => "off".to_string(),
+                };
+                format!("{}\t{}\t{}\t{}", id, title(alarm), describe_weekdays(alarm), next)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+// Alarms section of the clock popover
+pub fn create_alarm_section(service: Rc<AlarmService>) -> GtkBox {
+    let section = GtkBox::new(Orientation::Vertical, 4);
+    section.set_widget_name("clock-alarms");
+
+    let header = Label::new(Some("Alarms"));
+    header.add_css_class("heading");
+    header.set_halign(Align::Start);
+    section.append(&header);
+
+    let dismiss = Button::with_label("Dismiss");
+    dismiss.add_css_class("alarm-dismiss");
+    let service_clone = service.clone();
+    dismiss.connect_clicked(move |_| service_clone.dismiss());
+    section.append(&dismiss);
+
+    let list = GtkBox::new(Orientation::Vertical, 2);
+    section.append(&list);
+
+    // New alarm: time, weekdays and label
+    let form = GtkBox::new(Orientation::Horizontal, 4);
+    let time = Entry::new();
+    time.set_placeholder_text(Some("07:30"));
+    time.set_width_chars(5);
+    form.append(&time);
+
+    let days: Vec<ToggleButton> = all_weekdays()
+        .iter()
+        .map(|day| {
+            let name = weekday_name(*day);
+            let button = ToggleButton::with_label(&name[..1].to_uppercase());
+            button.set_tooltip_text(Some(&name));
+            button.add_css_class("alarm-day");
+            form.append(&button);
+            button
+        })
+        .collect();
+
+    let label = Entry::new();
+    label.set_placeholder_text(Some("Label"));
+    label.set_hexpand(true);
+    form.append(&label);
+
+    let add = Button::from_icon_name("list-add-symbolic");
+    let service_clone = service.clone();
+    let time_clone = time.clone();
+    add.connect_clicked(move |_| {
+        let weekdays = all_weekdays()
+            .iter()
+            .zip(&days)
+            .filter(|(_, button)| button.is_active())
+            .map(|(day, _)| weekday_name(*day))
+            .collect();
+        let alarm = Alarm {
+            time: time_clone.text().to_string(),
+            weekdays,
+            label: label.text().to_string(),
+            enabled: true,
+        };
+        match service_clone.add(alarm) {
+            Ok(()) => {
+                time_clone.set_text("");
+                label.set_text("");
+                time_clone.remove_css_class("error");
+                days.iter().for_each(|button| button.set_active(false));
+            }
+            Err(e) => {
+                time_clone.add_css_class("error");
+                time_clone.set_tooltip_text(Some(&e));
+            }
+        }
+    });
+    form.append(&add);
+    section.append(&form);
+
+    let fill = {
+        let list = list.downgrade();
+        let dismiss = dismiss.downgrade();
+        let service = Rc::downgrade(&service);
+        move || {
+            let (Some(list), Some(dismiss), Some(service)) = (list.upgrade(), dismiss.upgrade(), service.upgrade())
+            else {
+                return false;
+            };
+            dismiss.set_visible(service.is_ringing());
+            fill_alarms(&list, &service);
+            true
+        }
+    };
+    fill();
+    service.subscribe(fill);
+
+    section
+}
+
+fn fill_alarms(list: &GtkBox, service: &Rc<AlarmService>) {
+    while let Some(child) = list.first_child() {
+        list.remove(&child);
+    }
+
+    let now = now();
+    let mut index = 0;
+    for (alarm, editable) in service.alarms() {
+        let row = GtkBox::new(Orientation::Horizontal, 6);
+        row.add_css_class("alarm-row");
+
+        let next = match next_occurrence(&alarm, now) {
+            Some(at) => format_timestamp(at),
+            None => "off".to_string(),
+        };
+        let text = Label::new(Some(&format!("{}  ({})", title(&alarm), describe_weekdays(&alarm))));
+        text.set_tooltip_text(Some(&format!("Next: {}", next)));
+        text.set_hexpand(true);
+        text.set_halign(Align::Start);
+        text.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        row.append(&text);
+
+        if editable {
+            let position = index;
+            index += 1;
+
+            let switch = Switch::new();
+            switch.set_active(alarm.enabled);
+            switch.set_valign(Align::Center);
+            let service_clone = service.clone();
+            switch.connect_state_set(move |_, enabled| {
+                service_clone.set_enabled(position, enabled);
+                glib::Propagation::Proceed
+            });
+            row.append(&switch);
+
+            let remove = Button::from_icon_name("user-trash-symbolic");
+            let service_clone = service.clone();
+            remove.connect_clicked(move |_| {
+                if let Err(e) = service_clone.remove(position) {
+                    LOG.error(&e);
+                }
+            });
+            row.append(&remove);
+        } else {
+            let source = Label::new(Some("config"));
+            source.add_css_class("dim-label");
+            row.append(&source);
+        }
+
+        list.append(&row);
+    }
+}
@@ -0,0 +1,124 @@
+// Alarm schedules and the state kept across restarts
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, NaiveTime, TimeZone, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::config::config::Alarm;
+
+pub const ALARMS_STATE_FILE: &str = "alarms.toml";
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlarmState {
+    pub last_checked: i64,    // unix seconds, alarms up to here went off or were reported
+    pub alarms: Vec<Alarm>,   // added from the popover or over ipc
+    pub ringing: Vec<String>, // titles of alarms not dismissed yet
+}
+
+// "7:30", "07:30"
+pub fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M").ok()
+}
+
+// "mon", "monday", also "weekdays" and "weekend"
+pub fn parse_weekdays(names: &[String]) -> Result<Vec<Weekday>, String> {
+    let mut days = Vec::new();
+    for name in names {
+        match name.to_lowercase().as_str() {
+            "weekdays" => days.extend(&WEEKDAYS[..5]),
+            "weekend" => days.extend(&WEEKDAYS[5..]),
+            name => days.push(name.parse::<Weekday>().map_err(|_| format!("invalid weekday '{}'", name))?),
+        }
+    }
+    Ok(days)
+}
+
+pub fn weekday_name(day: Weekday) -> String {
+    day.to_string().to_lowercase()
+}
+
+pub fn all_weekdays() -> [Weekday; 7] {
+    WEEKDAYS
+}
+
+// Checks the time and weekdays of an alarm
+pub fn validate(alarm: &Alarm) -> Result<(), String> {
+    parse_time(&alarm.time).ok_or(format!("invalid time '{}', expected HH:MM", alarm.time))?;
+    parse_weekdays(&alarm.weekdays)?;
+    Ok(())
+}
+
+// Times the alarm goes off in (from, to], unix seconds
+pub fn occurrences(alarm: &Alarm, from: i64, to: i64) -> Vec<i64> {
+    let (Some(time), Ok(days)) = (parse_time(&alarm.time), parse_weekdays(&alarm.weekdays)) else {
+        return Vec::new();
+    };
+    if !alarm.enabled || to <= from {
+        return Vec::new();
+    }
+    let (Some(start), Some(end)) = (local(from), local(to)) else {
+        return Vec::new();
+    };
+
+    let mut result = Vec::new();
+    let mut date = start.date_naive();
+    while date <= end.date_naive() {
+        if (days.is_empty() || days.contains(&date.weekday()))
+            && let Some(at) = resolve_local(date.and_time(time))
+        {
+            let at = at.timestamp();
+            if at > from && at <= to {
+                result.push(at);
+            }
+        }
+        let Some(next) = date.succ_opt() else {
+            break;
+        };
+        date = next;
+    }
+    result
+}
+
+// Next time the alarm goes off after `now`, looks a week ahead
+pub fn next_occurrence(alarm: &Alarm, now: i64) -> Option<i64> {
+    occurrences(alarm, now, now + 8 * 24 * 3600).first().copied()
+}
+
+// Times skipped by a DST change go off at the first minute after the gap
+fn resolve_local(time: NaiveDateTime) -> Option<DateTime<Local>> {
+    (0..=3 * 60).find_map(|minutes| Local.from_local_datetime(&(time + Duration::minutes(minutes))).earliest())
+}
+
+fn local(timestamp: i64) -> Option<DateTime<Local>> {
+    Local.timestamp_opt(timestamp, 0).single()
+}
+
+pub fn title(alarm: &Alarm) -> String {
+    match alarm.label.as_str() {
+        "" => format!("Alarm {}", alarm.time),
+        label => format!("{} {}", alarm.time, label),
+    }
+}
+
+// "every day", "mon, wed"
+pub fn describe_weekdays(alarm: &Alarm) -> String {
+    match parse_weekdays(&alarm.weekdays) {
+        Ok(days) if days.is_empty() => "every day".to_string(),
+        Ok(days) => days.iter().map(|day| weekday_name(*day)).collect::<Vec<_>>().join(", "),
+        Err(e) => e,
+    }
+}
+
+// "Mon 07:30"
+pub fn format_timestamp(timestamp: i64) -> String {
+    local(timestamp).map(|at| at.format("%a %H:%M").to_string()).unwrap_or_default()
+}
@@ -0,0 +1,155 @@
+// Month calendar shown in the clock popover
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveTime};
+use gtk::{glib, prelude::*};
+use gtk::{Align, Box, Calendar, EventControllerScroll, EventControllerScrollFlags, Label, Orientation};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::config::config::ClockConfig;
+use crate::ui::modules::clock::calendar_helper::{load_events, occurrences, CalendarEvent, Occurrence};
+use crate::utils::paths::expand_home;
+
+// Month calendar with the events of the selected day, events are reloaded each time it is shown
+pub fn create_calendar_section(config: Rc<ClockConfig>) -> Box {
+    let content = Box::new(Orientation::Vertical, 6);
+    content.set_widget_name("clock-calendar");
+
+    let calendar = Calendar::new();
+    calendar.set_show_week_numbers(config.week_numbers);
+    content.append(&calendar);
+
+    let events_box = Box::new(Orientation::Vertical, 2);
+    events_box.set_widget_name("calendar-events");
+    content.append(&events_box);
+
+    let events: Rc<RefCell<Vec<CalendarEvent>>> = Rc::new(RefCell::new(Vec::new()));
+
+    // Scroll moves between months
+    let scroll = EventControllerScroll::new(EventControllerScrollFlags::VERTICAL | EventControllerScrollFlags::DISCRETE);
+    let calendar_clone = calendar.clone();
+    scroll.connect_scroll(move |_, _, dy| {
+        let months = if dy > 0.0 { 1 } else { -1 };
+        if let Ok(date) = calendar_clone.date().add_months(months) {
+            calendar_clone.select_day(&date);
+        }
+        glib::Propagation::Stop
+    });
+    calendar.add_controller(scroll);
+
+    // Marks follow the shown month, the list follows the selected day
+    let refresh = {
+        let calendar = calendar.clone();
+        let events = events.clone();
+        let events_box = events_box.clone();
+        let upcoming_days = config.upcoming_days;
+        Rc::new(move || {
+            let events = events.borrow();
+            mark_days(&calendar, &events);
+            fill_events(&events_box, &events, selected_date(&calendar), upcoming_days);
+        })
+    };
+    let refresh_clone = refresh.clone();
+    calendar.connect_day_selected(move |_| refresh_clone());
+    for signal in ["next-month", "prev-month", "next-year", "prev-year"] {
+        let refresh = refresh.clone();
+        calendar.connect_local(signal, false, move |_| {
+            refresh();
+            None
+        });
+    }
+
+    content.connect_map(move |_| {
+        // files are read on every open so edits show up without a restart
+        *events.borrow_mut() = match config.events_dir.as_str() {
+            "" => Vec::new(),
+            dir => load_events(&expand_home(dir)),
+        };
+        if let Ok(today) = glib::DateTime::now_local() {
+            calendar.select_day(&today);
+        }
+        refresh();
+    });
+
+    content
+}
+
+fn selected_date(calendar: &Calendar) -> Option<NaiveDate> {
+    let date = calendar.date();
+    NaiveDate::from_ymd_opt(date.year(), date.month() as u32, date.day_of_month() as u32)
+}
+
+fn mark_days(calendar: &Calendar, events: &[CalendarEvent]) {
+    calendar.clear_marks();
+    let Some(first) = selected_date(calendar).and_then(|date| date.with_day(1)) else {
+        return;
+    };
+    let Some(next) = first.checked_add_months(chrono::Months::new(1)) else {
+        return;
+    };
+
+    let from = first.and_time(NaiveTime::MIN);
+    for occurrence in occurrences(events, from, next.and_time(NaiveTime::MIN)) {
+        // all day events starting last month still mark the 1st
+        let day = occurrence.start.max(from).day();
+        calendar.mark_day(day);
+    }
+}
+
+fn fill_events(events_box: &Box, events: &[CalendarEvent], selected: Option<NaiveDate>, upcoming_days: u32) {
+    while let Some(child) = events_box.first_child() {
+        events_box.remove(&child);
+    }
+    let Some(selected) = selected else {
+        return;
+    };
+
+    let from = selected.and_time(NaiveTime::MIN);
+    let day_events = occurrences(events, from, from + Duration::days(1));
+    add_section(events_box, &selected.format("%A, %d %B").to_string(), &day_events, false);
+
+    // upcoming events are only listed when looking at today
+    let today = Local::now().date_naive();
+    if selected == today && upcoming_days > 0 {
+        let start = from + Duration::days(1);
+        let upcoming = occurrences(events, start, start + Duration::days(upcoming_days as i64));
+        if !upcoming.is_empty() {
+            add_section(events_box, "Upcoming", &upcoming, true);
+        }
+    }
+}
+
+fn add_section(events_box: &Box, title: &str, list: &[Occurrence], show_date: bool) {
+    let header = Label::new(Some(title));
+    header.add_css_class("heading");
+    header.set_halign(Align::Start);
+    events_box.append(&header);
+
+    if list.is_empty() {
+        let empty = Label::new(Some("No events"));
+        empty.add_css_class("dim-label");
+        empty.set_halign(Align::Start);
+        events_box.append(&empty);
+        return;
+    }
+
+    for event in list {
+        let when = match (show_date, event.all_day) {
+            (true, true) => event.start.format("%a %d").to_string(),
+            (true, false) => event.start.format("%a %d %H:%M").to_string(),
+            (false, true) => "All day".to_string(),
+            (false, false) => event.start.format("%H:%M").to_string(),
+        };
+        let mut text = format!("<b>{}</b>  {}", when, glib::markup_escape_text(&event.summary));
+        if !event.location.is_empty() {
+            text.push_str(&format!(" <small>({})</small>", glib::markup_escape_text(&event.location)));
+        }
+
+        let label = Label::new(None);
+        label.set_markup(&text);
+        label.set_halign(Align::Start);
+        label.set_max_width_chars(40);
+        label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        label.add_css_class("calendar-event");
+        events_box.append(&label);
+    }
+}
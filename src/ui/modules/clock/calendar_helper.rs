@@ -0,0 +1,336 @@
+// Events from local .ics files for the clock calendar
+// Supports single events and simple RRULEs (FREQ, INTERVAL, COUNT, UNTIL) with EXDATE and RECURRENCE-ID
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use glib::{DateTime, TimeZone};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("calendar",LogLevel::Debug);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone)]
+struct Recurrence {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub location: String,
+    pub start: NaiveDateTime, // local time
+    pub all_day: bool,
+    recurrence: Option<Recurrence>,
+    exdates: Vec<NaiveDateTime>, // deleted or moved occurrences
+}
+
+// One occurrence of an event
+#[derive(Debug, Clone)]
+pub struct Occurrence {
+    pub summary: String,
+    pub location: String,
+    pub start: NaiveDateTime,
+    pub all_day: bool,
+}
+
+// Events of every .ics file in a directory and its subdirectories,
+// vdirsyncer and khal keep one subdirectory per calendar
+pub fn load_events(dir: &Path) -> Vec<CalendarEvent> {
+    let mut files = Vec::new();
+    if let Err(e) = find_ics_files(dir, &mut files) {
+        LOG.error(&format!("Failed to read events directory {}: {}", dir.display(), e));
+        return Vec::new();
+    }
+
+    files
+        .into_iter()
+        .filter_map(|path| match fs::read_to_string(&path) {
+            Ok(content) => Some(parse_ics(&content)),
+            Err(e) => {
+                LOG.error(&format!("Failed to read {}: {}", path.display(), e));
+                None
+            }
+        })
+        .flatten()
+        .collect()
+}
+
+fn find_ics_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        // file_type does not follow symlinks so linked directories can not loop
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            if let Err(e) = find_ics_files(&path, files) {
+                LOG.error(&format!("Failed to read {}: {}", path.display(), e));
+            }
+        } else if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("ics")) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// Occurrences starting between from and to, sorted by start
+pub fn occurrences(events: &[CalendarEvent], from: NaiveDateTime, to: NaiveDateTime) -> Vec<Occurrence> {
+    let mut result: Vec<Occurrence> = Vec::new();
+
+    for event in events {
+        let mut start = event.start;
+        let mut index = 0;
+        loop {
+            if start >= to {
+                break;
+            }
+            // all day events are shown for the whole day
+            let end = if event.all_day { start + Duration::days(1) } else { start };
+            if end > from && !event.exdates.contains(&start) {
+                result.push(Occurrence {
+                    summary: event.summary.clone(),
+                    location: event.location.clone(),
+                    start,
+                    all_day: event.all_day,
+                });
+            }
+
+            let Some(recurrence) = &event.recurrence else {
+                break;
+            };
+            index += 1;
+            if recurrence.count.is_some_and(|count| index >= count) {
+                break;
+            }
+            let Some(next) = next_occurrence(event.start, recurrence, index) else {
+                break;
+            };
+            if recurrence.until.is_some_and(|until| next > until) {
+                break;
+            }
+            start = next;
+        }
+    }
+
+    result.sort_by_key(|o| o.start);
+    result
+}
+
+// nth repetition from the first start, computed from the start so month ends do not drift
+fn next_occurrence(first: NaiveDateTime, recurrence: &Recurrence, index: u32) -> Option<NaiveDateTime> {
+    let steps = index * recurrence.interval.max(1);
+    match recurrence.frequency {
+        Frequency::Daily => Some(first + Duration::days(steps as i64)),
+        Frequency::Weekly => Some(first + Duration::weeks(steps as i64)),
+        Frequency::Monthly => first.checked_add_months(Months::new(steps)),
+        Frequency::Yearly => first.checked_add_months(Months::new(steps * 12)),
+    }
+}
+
+// Join folded lines, continuation lines start with a space or tab
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+// VEVENT properties read so far, events without DTSTART are dropped
+#[derive(Default)]
+struct PartialEvent {
+    uid: String,
+    summary: String,
+    location: String,
+    start: Option<(NaiveDateTime, bool)>,
+    recurrence: Option<Recurrence>,
+    recurrence_id: Option<NaiveDateTime>, // occurrence of the recurring event with the same UID this replaces
+    exdates: Vec<NaiveDateTime>,
+    cancelled: bool,
+}
+
+fn parse_ics(content: &str) -> Vec<CalendarEvent> {
+    let mut parsed: Vec<PartialEvent> = Vec::new();
+    let mut current: Option<PartialEvent> = None;
+    // VALARM and other components inside the event, their properties are not the event's
+    let mut nested = 0;
+
+    for line in unfold(content) {
+        if let Some(component) = line.strip_prefix("BEGIN:") {
+            match current {
+                Some(_) => nested += 1,
+                None if component == "VEVENT" => current = Some(PartialEvent::default()),
+                None => {}
+            }
+            continue;
+        }
+        if let Some(component) = line.strip_prefix("END:") {
+            if nested > 0 {
+                nested -= 1;
+            } else if component == "VEVENT"
+                && let Some(event) = current.take()
+            {
+                parsed.push(event);
+            }
+            continue;
+        }
+
+        let Some(event) = current.as_mut() else {
+            continue;
+        };
+        if nested > 0 {
+            continue;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // name may carry parameters eg: "DTSTART;TZID=Europe/Berlin"
+        let mut params = name.split(';');
+        let property = params.next().unwrap_or_default();
+        let tzid = params.find_map(|p| p.strip_prefix("TZID="));
+        match property {
+            "UID" => event.uid = value.to_string(),
+            "SUMMARY" => event.summary = unescape(value),
+            "LOCATION" => event.location = unescape(value),
+            "STATUS" => event.cancelled = value == "CANCELLED",
+            "DTSTART" => event.start = parse_date_time(value, tzid),
+            "RRULE" => event.recurrence = parse_rrule(value),
+            "RECURRENCE-ID" => event.recurrence_id = parse_date_time(value, tzid).map(|(time, _)| time),
+            "EXDATE" => event
+                .exdates
+                .extend(value.split(',').filter_map(|value| parse_date_time(value, tzid)).map(|(time, _)| time)),
+            _ => {}
+        }
+    }
+
+    // moved and cancelled occurrences are left out of the recurring event
+    let replaced: Vec<(String, NaiveDateTime)> = parsed
+        .iter()
+        .filter_map(|event| Some((event.uid.clone(), event.recurrence_id?)))
+        .collect();
+    for event in parsed.iter_mut().filter(|event| event.recurrence_id.is_none()) {
+        let uid = &event.uid;
+        event.exdates.extend(replaced.iter().filter(|(id, _)| id == uid).map(|(_, time)| *time));
+    }
+
+    parsed
+        .into_iter()
+        .filter(|event| !event.cancelled)
+        .filter_map(|event| {
+            let (start, all_day) = event.start?;
+            Some(CalendarEvent {
+                summary: event.summary,
+                location: event.location,
+                start,
+                all_day,
+                // a replacement is a single occurrence
+                recurrence: event.recurrence.filter(|_| event.recurrence_id.is_none()),
+                exdates: event.exdates,
+            })
+        })
+        .collect()
+}
+
+// "20250101" all day, "20250101T090000" floating or in tzid, "20250101T090000Z" UTC
+fn parse_date_time(value: &str, tzid: Option<&str>) -> Option<(NaiveDateTime, bool)> {
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_time(NaiveTime::MIN), true));
+    }
+
+    let (value, utc) = match value.strip_suffix('Z') {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+
+    let timezone = match (utc, tzid) {
+        (true, _) => TimeZone::utc(),
+        (false, Some(id)) => match TimeZone::from_identifier(Some(id)) {
+            Some(timezone) => timezone,
+            // eg: Outlook's "W. Europe Standard Time" or a custom VTIMEZONE id
+            None => {
+                LOG.error(&format!("Unknown TZID '{}', using local time", id));
+                return Some((time, false));
+            }
+        },
+        (false, None) => return Some((time, false)),
+    };
+    Some((to_local(time, &timezone)?, false))
+}
+
+fn to_local(time: NaiveDateTime, timezone: &TimeZone) -> Option<NaiveDateTime> {
+    let local = DateTime::new(
+        timezone,
+        time.year(),
+        time.month() as i32,
+        time.day() as i32,
+        time.hour() as i32,
+        time.minute() as i32,
+        time.second() as f64,
+    )
+    .ok()?
+    .to_local()
+    .ok()?;
+
+    NaiveDate::from_ymd_opt(local.year(), local.month() as u32, local.day_of_month() as u32)?
+        .and_hms_opt(local.hour() as u32, local.minute() as u32, local.second() as u32)
+}
+
+// "FREQ=WEEKLY;INTERVAL=2;COUNT=10", rules with BYDAY and the like repeat on the start day only
+fn parse_rrule(value: &str) -> Option<Recurrence> {
+    let mut recurrence = Recurrence {
+        frequency: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+    };
+    let mut frequency = None;
+
+    for part in value.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        match key {
+            "FREQ" => {
+                frequency = match value {
+                    "DAILY" => Some(Frequency::Daily),
+                    "WEEKLY" => Some(Frequency::Weekly),
+                    "MONTHLY" => Some(Frequency::Monthly),
+                    "YEARLY" => Some(Frequency::Yearly),
+                    _ => None,
+                }
+            }
+            "INTERVAL" => recurrence.interval = value.parse().unwrap_or(1),
+            "COUNT" => recurrence.count = value.parse().ok(),
+            "UNTIL" => recurrence.until = parse_date_time(value, None).map(|(until, _)| until),
+            _ => {}
+        }
+    }
+
+    recurrence.frequency = frequency?;
+    Some(recurrence)
+}
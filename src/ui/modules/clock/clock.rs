@@ -0,0 +1,241 @@
+// Clock module
+
+use gtk::{gdk, glib, prelude::*, Box as GtkBox, EventControllerScroll, EventControllerScrollFlags, GestureClick, Label, Orientation, Popover, Separator};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::config::config::{AlarmConfig, ClockConfig, TimerConfig};
+use crate::ui::bar::set_keyboard_focus;
+use crate::ui::modules::clock::alarm::{create_alarm_section, AlarmService};
+use crate::ui::modules::clock::calendar::create_calendar_section;
+use crate::ui::modules::clock::clock_helper::{format_time, get_timezone, has_seconds, next_tick_delay};
+use crate::ui::modules::clock::timer::{create_timer_section, now, TimerService};
+use crate::ui::modules::clock::timer_helper::format_timer;
+
+#[derive(Clone)]
+pub struct ClockModule {
+    container: GtkBox,
+    label: Label,
+    timer_label: Label,
+    config: Rc<ClockConfig>,
+    timer_config: Rc<TimerConfig>,
+    timers: Rc<TimerService>,
+    alarms: Rc<AlarmService>,
+    show_alt: Rc<Cell<bool>>,        // format_alt toggled by right click
+    timezone_index: Rc<Cell<usize>>, // 0 is local, then config.timezones
+    tick_source: Rc<RefCell<Option<glib::SourceId>>>, // pending aligned tick
+}
+
+impl ClockModule {
+    pub fn new(config: ClockConfig, timer_config: TimerConfig, alarm_config: AlarmConfig) -> Self {
+        Self {
+            container: GtkBox::new(Orientation::Horizontal, 0),
+            label: Label::new(Some("")),
+            timer_label: Label::new(None),
+            config: Rc::new(config),
+            timers: TimerService::get(&timer_config),
+            alarms: AlarmService::get(&alarm_config),
+            timer_config: Rc::new(timer_config),
+            show_alt: Rc::new(Cell::new(false)),
+            timezone_index: Rc::new(Cell::new(0)),
+            tick_source: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    pub fn create(&self) -> GtkBox {
+        let time_label = self.label.clone();
+        time_label.set_widget_name("clock");
+        time_label.add_css_class("modules");
+        self.container.append(&time_label);
+
+        // Running timer next to the time
+        self.timer_label.set_widget_name("clock-timer");
+        self.timer_label.add_css_class("modules");
+        self.timer_label.set_visible(false);
+        self.container.append(&self.timer_label);
+
+        // weak refs so the services drop the listeners once the bar is destroyed
+        let container = self.container.downgrade();
+        let timer_label = self.timer_label.downgrade();
+        let timers = Rc::downgrade(&self.timers);
+        self.timers.subscribe(move || {
+            let (Some(container), Some(timer_label), Some(timers)) =
+                (container.upgrade(), timer_label.upgrade(), timers.upgrade())
+            else {
+                return false;
+            };
+            update_timer(&container, &timer_label, &timers);
+            true
+        });
+        update_timer(&self.container, &self.timer_label, &self.timers);
+
+        // Highlighted until the alarm is dismissed
+        let container = self.container.downgrade();
+        let alarms = Rc::downgrade(&self.alarms);
+        self.alarms.subscribe(move || {
+            let (Some(container), Some(alarms)) = (container.upgrade(), alarms.upgrade()) else {
+                return false;
+            };
+            update_alarm(&container, &alarms);
+            true
+        });
+        update_alarm(&self.container, &self.alarms);
+
+        // Left click opens the calendar, timers and alarms or dismisses a ringing alarm
+        // right click toggles format_alt
+        self.setup_popover();
+
+        let click = GestureClick::new();
+        click.set_button(gdk::BUTTON_SECONDARY);
+        let clock = self.clone();
+        click.connect_pressed(move |gesture, _, x, y| {
+            if !gesture.widget().is_some_and(|w| w.contains(x, y)) {
+                return;
+            }
+            // claim it first so the bar context menu does not open
+            gesture.set_state(gtk::EventSequenceState::Claimed);
+            clock.show_alt.set(!clock.show_alt.get());
+            clock.update();
+            // the other format may tick on seconds, realign right away
+            if let Some(source) = clock.tick_source.borrow_mut().take() {
+                source.remove();
+            }
+            clock.schedule_tick();
+        });
+        time_label.add_controller(click);
+
+        // Scroll cycles the extra timezones
+        if self.config.scroll_timezones && !self.config.timezones.is_empty() {
+            let scroll = EventControllerScroll::new(
+                EventControllerScrollFlags::VERTICAL | EventControllerScrollFlags::DISCRETE,
+            );
+            let clock = self.clone();
+            scroll.connect_scroll(move |_, _, dy| {
+                let count = clock.config.timezones.len() + 1;
+                let index = clock.timezone_index.get();
+                let next = if dy > 0.0 { (index + 1) % count } else { (index + count - 1) % count };
+                clock.timezone_index.set(next);
+                clock.update();
+                glib::Propagation::Stop
+            });
+            time_label.add_controller(scroll);
+        }
+
+        self.container.clone()
+    }
+
+    fn setup_popover(&self) {
+        let content = GtkBox::new(Orientation::Vertical, 8);
+        content.set_widget_name("clock-popover");
+        if self.config.calendar {
+            content.append(&create_calendar_section(self.config.clone()));
+            content.append(&Separator::new(Orientation::Horizontal));
+        }
+        content.append(&create_timer_section(self.timers.clone(), &self.timer_config.presets));
+        content.append(&Separator::new(Orientation::Horizontal));
+        content.append(&create_alarm_section(self.alarms.clone()));
+
+        let popover = Popover::new();
+        popover.set_parent(&self.container);
+        popover.set_autohide(true);
+        popover.set_child(Some(&content));
+        // the timer and alarm entries need keyboard input while open
+        popover.connect_show(|popover| set_keyboard_focus(popover, true));
+        popover.connect_closed(|popover| set_keyboard_focus(popover, false));
+
+        let gesture = GestureClick::new();
+        gesture.set_button(gdk::BUTTON_PRIMARY);
+        let alarms = self.alarms.clone();
+        gesture.connect_released(move |gesture, _, x, y| {
+            // clicks inside the popover bubble up to the clock too
+            if !gesture.widget().is_some_and(|w| w.contains(x, y)) {
+                return;
+            }
+            if alarms.is_ringing() {
+                alarms.dismiss();
+            } else if popover.is_visible() {
+                popover.popdown();
+            } else {
+                popover.popup();
+            }
+        });
+        self.container.add_controller(gesture);
+    }
+
+    fn format(&self) -> &str {
+        if self.show_alt.get() {
+            &self.config.format_alt
+        } else {
+            &self.config.format
+        }
+    }
+
+    // Zone shown on the bar, None for local
+    fn timezone(&self) -> Option<&str> {
+        match self.timezone_index.get() {
+            0 => None,
+            i => self.config.timezones.get(i - 1).map(String::as_str),
+        }
+    }
+
+    pub fn update(&self) {
+        let timezone = get_timezone(self.timezone());
+        let mut text = format_time(self.format(), &timezone);
+        if let Some(name) = self.timezone() {
+            text = format!("{} {}", text, name.rsplit('/').next().unwrap_or(name));
+        }
+        self.label.set_text(&text);
+
+        if self.config.tooltip {
+            let mut tooltip = format_time(&self.config.tooltip_format, &get_timezone(None));
+            for name in &self.config.timezones {
+                let time = format_time(&self.config.format, &get_timezone(Some(name)));
+                tooltip.push_str(&format!("\n{}: {}", glib::markup_escape_text(name), time));
+            }
+            self.label.set_tooltip_markup(Some(&tooltip));
+        }
+    }
+
+    // Update now and then on every second or minute boundary
+    pub fn start_updates(&self) {
+        self.update();
+        self.schedule_tick();
+    }
+
+    fn schedule_tick(&self) {
+        let clock = self.clone();
+        let delay = next_tick_delay(has_seconds(self.format()));
+        let source = glib::timeout_add_local_once(delay, move || {
+            clock.tick_source.borrow_mut().take();
+            clock.update();
+            clock.schedule_tick();
+        });
+        *self.tick_source.borrow_mut() = Some(source);
+    }
+}
+
+fn update_timer(container: &GtkBox, timer_label: &Label, timers: &TimerService) {
+    let store = timers.store();
+    let now = now();
+    let Some(timer) = store.shown(now) else {
+        timer_label.set_visible(false);
+        container.remove_css_class("running");
+        return;
+    };
+
+    let running = store.timers.iter().filter(|timer| timer.is_running()).count();
+    timer_label.set_text(&format_timer(timers.format(), timer, running, now));
+    timer_label.set_visible(true);
+    container.add_css_class("running");
+}
+
+fn update_alarm(container: &GtkBox, alarms: &AlarmService) {
+    let ringing = alarms.ringing();
+    if ringing.is_empty() {
+        container.remove_css_class("alarm");
+        container.set_tooltip_text(None);
+    } else {
+        container.add_css_class("alarm");
+        container.set_tooltip_text(Some(&format!("{}\nClick to dismiss", ringing.join("\n"))));
+    }
+}
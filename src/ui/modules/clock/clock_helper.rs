@@ -0,0 +1,42 @@
+use glib::{DateTime, TimeZone};
+use std::time::Duration;
+
+// Zone from an IANA identifier eg: "Europe/London", local zone if empty or unknown
+pub fn get_timezone(identifier: Option<&str>) -> TimeZone {
+    identifier
+        .filter(|id| !id.is_empty())
+        .and_then(|id| TimeZone::from_identifier(Some(id)))
+        .unwrap_or_else(TimeZone::local)
+}
+
+// Format the current time in a zone
+// GDateTime uses the locale of the bar for names of days and months
+pub fn format_time(format: &str, timezone: &TimeZone) -> String {
+    DateTime::now(timezone)
+        .and_then(|now| now.format(format))
+        .map(|text| text.to_string())
+        .unwrap_or_default()
+}
+
+// Whether the format changes every second
+pub fn has_seconds(format: &str) -> bool {
+    ["%S", "%T", "%s", "%X", "%r", "%c", "%f"]
+        .iter()
+        .any(|specifier| format.contains(specifier))
+}
+
+// Time until the next second or minute starts, so the clock changes right on the boundary
+pub fn next_tick_delay(seconds: bool) -> Duration {
+    let Ok(now) = DateTime::now_local() else {
+        return Duration::from_secs(1);
+    };
+
+    let micros_in_second = 1_000_000 - now.microsecond() as u64;
+    let micros = if seconds {
+        micros_in_second
+    } else {
+        (59 - now.second() as u64) * 1_000_000 + micros_in_second
+    };
+    // wake up just after the boundary, not just before it
+    Duration::from_micros(micros) + Duration::from_millis(5)
+}
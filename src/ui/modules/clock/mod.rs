@@ -0,0 +1,8 @@
+pub mod alarm;
+pub mod alarm_helper;
+pub mod calendar;
+pub mod calendar_helper;
+pub mod clock;
+pub mod clock_helper;
+pub mod timer;
+pub mod timer_helper;
\ No newline at end of file
@@ -0,0 +1,441 @@
+// Timers shared by the clocks of every bar, controlled from the popover or over ipc
+use gtk::{glib, prelude::*};
+use gtk::{Align, Box as GtkBox, Button, Entry, Label, Orientation};
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use crate::config::config::TimerConfig;
+use crate::system::ipc;
+use crate::ui::modules::clock::timer_helper::{
+    format_duration, parse_duration, PomodoroPhase, Timer, TimerKind, TimerStore, TIMERS_STATE_FILE,
+};
+use crate::utils::notify::{run_command, send_notification};
+use crate::utils::state::{load_state, save_state};
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("timer",LogLevel::Debug);
+}
+
+thread_local! {
+    static SERVICE: RefCell<Option<Rc<TimerService>>> = const { RefCell::new(None) };
+}
+
+pub fn now() -> i64 {
+    glib::real_time() / 1_000_000
+}
+
+pub struct TimerService {
+    config: TimerConfig,
+    store: RefCell<TimerStore>,
+    listeners: RefCell<Vec<Box<dyn Fn() -> bool>>>,
+    ticking: Cell<bool>,
+}
+
+impl TimerService {
+    // The first clock creates the service, later ones share it
+    pub fn get(config: &TimerConfig) -> Rc<TimerService> {
+        SERVICE.with(|service| {
+            service
+                .borrow_mut()
+                .get_or_insert_with(|| {
+                    let service = Rc::new(TimerService {
+                        config: config.clone(),
+                        store: RefCell::new(load_state(TIMERS_STATE_FILE)),
+                        listeners: RefCell::new(Vec::new()),
+                        ticking: Cell::new(false),
+                    });
+                    service.register_ipc();
+                    service.ensure_ticking();
+                    service
+                })
+                .clone()
+        })
+    }
+
+    // Called after every change and every second while a timer runs,
+    // the listener is dropped once it returns false
+    pub fn subscribe(&self, listener: impl Fn() -> bool + 'static) {
+        self.listeners.borrow_mut().push(Box::new(listener));
+    }
+
+    pub fn store(&self) -> std::cell::Ref<'_, TimerStore> {
+        self.store.borrow()
+    }
+
+    pub fn format(&self) -> &str {
+        &self.config.format
+    }
+
+    fn changed(self: &Rc<Self>) {
+        if let Err(e) = save_state(TIMERS_STATE_FILE, &*self.store.borrow()) {
+            LOG.error(&format!("Failed to save timers: {}", e));
+        }
+        self.ensure_ticking();
+        self.notify_listeners();
+    }
+
+    fn notify_listeners(&self) {
+        self.listeners.borrow_mut().retain(|listener| listener());
+    }
+
+    pub fn start_countdown(self: &Rc<Self>, duration: u64, label: String) -> u32 {
+        let id = self.store.borrow_mut().new_id();
+        self.store.borrow_mut().timers.push(Timer::countdown(id, duration, label, now()));
+        self.changed();
+        id
+    }
+
+    pub fn start_stopwatch(self: &Rc<Self>, label: String) -> u32 {
+        let id = self.store.borrow_mut().new_id();
+        self.store.borrow_mut().timers.push(Timer::stopwatch(id, label, now()));
+        self.changed();
+        id
+    }
+
+    // Only one pomodoro cycle at a time, starting again restarts it
+    pub fn start_pomodoro(self: &Rc<Self>) -> u32 {
+        let id = {
+            let mut store = self.store.borrow_mut();
+            store.timers.retain(|timer| timer.kind != TimerKind::Pomodoro);
+            let id = store.new_id();
+            store.timers.push(Timer::pomodoro(id, &self.config, now()));
+            id
+        };
+        self.changed();
+        id
+    }
+
+    pub fn toggle(self: &Rc<Self>, id: u32) -> bool {
+        self.with_timer(id, |timer| {
+            if timer.is_running() {
+                timer.pause(now());
+            } else {
+                timer.resume(now());
+            }
+        })
+    }
+
+    pub fn set_running(self: &Rc<Self>, id: u32, running: bool) -> bool {
+        self.with_timer(id, |timer| {
+            if running {
+                timer.resume(now());
+            } else {
+                timer.pause(now());
+            }
+        })
+    }
+
+    pub fn stop(self: &Rc<Self>, id: u32) -> bool {
+        let removed = {
+            let mut store = self.store.borrow_mut();
+            let count = store.timers.len();
+            store.timers.retain(|timer| timer.id != id);
+            store.timers.len() != count
+        };
+        if removed {
+            self.changed();
+        }
+        removed
+    }
+
+    fn with_timer(self: &Rc<Self>, id: u32, f: impl FnOnce(&mut Timer)) -> bool {
+        let found = match self.store.borrow_mut().timers.iter_mut().find(|timer| timer.id == id) {
+            Some(timer) => {
+                f(timer);
+                true
+            }
+            None => false,
+        };
+        if found {
+            self.changed();
+        }
+        found
+    }
+
+    // Tick each second while any timer runs, stops by itself when none do
+    fn ensure_ticking(self: &Rc<Self>) {
+        let running = self.store.borrow().timers.iter().any(Timer::is_running);
+        if !running || self.ticking.replace(true) {
+            return;
+        }
+
+        let weak: Weak<Self> = Rc::downgrade(self);
+        glib::timeout_add_local(Duration::from_secs(1), move || {
+            let Some(service) = weak.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            service.tick();
+            if service.store.borrow().timers.iter().any(Timer::is_running) {
+                glib::ControlFlow::Continue
+            } else {
+                service.ticking.set(false);
+                glib::ControlFlow::Break
+            }
+        });
+        // timers that ended while the bar was closed fire right away
+        self.tick();
+    }
+
+    fn tick(self: &Rc<Self>) {
+        let now = now();
+        let finished: Vec<Timer> = {
+            let mut store = self.store.borrow_mut();
+            let finished = store.timers.iter().filter(|timer| timer.is_finished(now)).cloned().collect();
+            store.timers.retain(|timer| timer.kind == TimerKind::Pomodoro || !timer.is_finished(now));
+            for timer in store.timers.iter_mut().filter(|timer| timer.is_finished(now)) {
+                timer.next_phase(&self.config, now);
+            }
+            finished
+        };
+
+        if finished.is_empty() {
+            self.notify_listeners();
+            return;
+        }
+        for timer in &finished {
+            self.finished(timer);
+        }
+        self.changed();
+    }
+
+    fn finished(&self, timer: &Timer) {
+        LOG.debug(&format!("Timer {} finished", timer.id));
+        if self.config.notify {
+            let (title, body) = match (timer.kind, timer.phase) {
+                (TimerKind::Pomodoro, PomodoroPhase::Work) => ("Pomodoro".to_string(), "Time for a break".to_string()),
+                (TimerKind::Pomodoro, _) => ("Pomodoro".to_string(), "Break is over, back to work".to_string()),
+                _ => ("Timer finished".to_string(), timer.title()),
+            };
+            send_notification(&format!("timer-{}", timer.id), &title, &body);
+        }
+        run_command(&self.config.sound_command);
+    }
+
+    fn register_ipc(self: &Rc<Self>) {
+        let weak = Rc::downgrade(self);
+        ipc::register("timer", move |args| match weak.upgrade() {
+            Some(service) => service.handle_command(args),
+            None => Err("timers are not available".to_string()),
+        });
+    }
+
+    // timer start <duration> [label] | stopwatch [label] | pomodoro | pause|resume|toggle|stop [id|all] | list
+    fn handle_command(self: &Rc<Self>, args: &[String]) -> Result<String, String> {
+        let command = args.first().map(String::as_str).unwrap_or("list");
+        let rest = args.get(1..).unwrap_or_default();
+        let label = || rest.get(1..).unwrap_or_default().join(" ");
+
+        match command {
+            "start" => {
+                let duration = rest
+                    .first()
+                    .and_then(|text| parse_duration(text))
+                    .ok_or("usage: timer start <duration eg: 25m, 1h30m, 90s> [label]")?;
+                Ok(self.start_countdown(duration, label()).to_string())
+            }
+            "stopwatch" => Ok(self.start_stopwatch(rest.join(" ")).to_string()),
+            "pomodoro" => Ok(self.start_pomodoro().to_string()),
+            "pause" | "resume" | "toggle" | "stop" => {
+                let ids = self.target_ids(rest.first().map(String::as_str))?;
+                for id in ids {
+                    match command {
+                        "pause" => self.set_running(id, false),
+                        "resume" => self.set_running(id, true),
+                        "toggle" => self.toggle(id),
+                        _ => self.stop(id),
+                    };
+                }
+                Ok(String::new())
+            }
+            "list" => Ok(self.list()),
+            _ => Err(format!("unknown timer command '{}'", command)),
+        }
+    }
+
+    // No id means the newest timer
+    fn target_ids(&self, target: Option<&str>) -> Result<Vec<u32>, String> {
+        let store = self.store.borrow();
+        match target {
+            Some("all") => Ok(store.timers.iter().map(|timer| timer.id).collect()),
+            Some(id) => {
+                let id: u32 = id.parse().map_err(|_| format!("invalid timer id '{}'", id))?;
+                match store.timers.iter().any(|timer| timer.id == id) {
+                    true => Ok(vec![id]),
+                    false => Err(format!("no timer with id {}", id)),
+                }
+            }
+            None => store.timers.last().map(|timer| vec![timer.id]).ok_or("no timers".to_string()),
+        }
+    }
+
+    fn list(&self) -> String {
+        let now = now();
+        self.store
+            .borrow()
+            .timers
+            .iter()
+            .map(|timer| {
+                let state = if timer.is_running() { "running" } else { "paused" };
+                format!("{}\t{}\t{}\t{}", timer.id, timer.display_time(now), state, timer.title())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+// Timers section of the clock popover
+pub fn create_timer_section(service: Rc<TimerService>, presets: &[String]) -> GtkBox {
+    let section = GtkBox::new(Orientation::Vertical, 4);
+    section.set_widget_name("clock-timers");
+
+    let header = Label::new(Some("Timers"));
+    header.add_css_class("heading");
+    header.set_halign(Align::Start);
+    section.append(&header);
+
+    let list = GtkBox::new(Orientation::Vertical, 2);
+    section.append(&list);
+
+    let buttons = GtkBox::new(Orientation::Horizontal, 4);
+    for preset in presets {
+        let Some(duration) = parse_duration(preset) else {
+            LOG.error(&format!("Invalid timer preset '{}'", preset));
+            continue;
+        };
+        let button = Button::with_label(&format_duration(duration));
+        let service = service.clone();
+        button.connect_clicked(move |_| {
+            service.start_countdown(duration, String::new());
+        });
+        buttons.append(&button);
+    }
+
+    let stopwatch = Button::with_label("Stopwatch");
+    let service_clone = service.clone();
+    stopwatch.connect_clicked(move |_| {
+        service_clone.start_stopwatch(String::new());
+    });
+    buttons.append(&stopwatch);
+
+    let pomodoro = Button::with_label("Pomodoro");
+    let service_clone = service.clone();
+    pomodoro.connect_clicked(move |_| {
+        service_clone.start_pomodoro();
+    });
+    buttons.append(&pomodoro);
+    section.append(&buttons);
+
+    // Custom duration eg: "45m" or "1h tea"
+    let entry = Entry::new();
+    entry.set_placeholder_text(Some("Duration and label eg: 45m tea"));
+    let service_clone = service.clone();
+    entry.connect_activate(move |entry| {
+        let text = entry.text();
+        let mut words = text.split_whitespace();
+        match words.next().and_then(parse_duration) {
+            Some(duration) => {
+                service_clone.start_countdown(duration, words.collect::<Vec<_>>().join(" "));
+                entry.set_text("");
+                entry.remove_css_class("error");
+            }
+            None => entry.add_css_class("error"),
+        }
+    });
+    section.append(&entry);
+
+    // rows are rebuilt when timers are added, removed or paused, a tick only updates the times
+    let shown: Rc<RefCell<Vec<(u32, bool, String)>>> = Rc::new(RefCell::new(Vec::new()));
+    let fill = {
+        let list = list.downgrade();
+        let service = Rc::downgrade(&service);
+        move || {
+            let (Some(list), Some(service)) = (list.upgrade(), service.upgrade()) else {
+                return false;
+            };
+            if !list.is_mapped() && list.first_child().is_some() {
+                return true;
+            }
+            let key = service.store().timers.iter().map(|t| (t.id, t.is_running(), t.title())).collect::<Vec<_>>();
+            if *shown.borrow() == key {
+                update_timer_times(&list, &service);
+            } else {
+                fill_timers(&list, &service);
+                *shown.borrow_mut() = key;
+            }
+            true
+        }
+    };
+    fill();
+    let fill = Rc::new(fill);
+    let fill_clone = fill.clone();
+    list.connect_map(move |_| {
+        fill_clone();
+    });
+    service.subscribe(move || fill());
+
+    section
+}
+
+// Rows are in store order and start with the time label
+fn update_timer_times(list: &GtkBox, service: &TimerService) {
+    let now = now();
+    let mut row = list.first_child();
+    for timer in service.store().timers.iter() {
+        let Some(current) = row else {
+            break;
+        };
+        if let Some(time) = current.first_child().and_downcast::<Label>() {
+            time.set_text(&format!("{} {}", timer.icon(), timer.display_time(now)));
+        }
+        row = current.next_sibling();
+    }
+}
+
+fn fill_timers(list: &GtkBox, service: &Rc<TimerService>) {
+    while let Some(child) = list.first_child() {
+        list.remove(&child);
+    }
+
+    let now = now();
+    for timer in service.store().timers.iter() {
+        let row = GtkBox::new(Orientation::Horizontal, 6);
+        row.add_css_class("timer-row");
+        if timer.is_running() {
+            row.add_css_class("running");
+        }
+
+        let time = Label::new(Some(&format!("{} {}", timer.icon(), timer.display_time(now))));
+        time.add_css_class("timer-time");
+        row.append(&time);
+
+        let title = Label::new(Some(&timer.title()));
+        title.set_hexpand(true);
+        title.set_halign(Align::Start);
+        title.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        row.append(&title);
+
+        let id = timer.id;
+        let toggle = Button::from_icon_name(if timer.is_running() {
+            "media-playback-pause-symbolic"
+        } else {
+            "media-playback-start-symbolic"
+        });
+        let service_clone = service.clone();
+        toggle.connect_clicked(move |_| {
+            service_clone.toggle(id);
+        });
+        row.append(&toggle);
+
+        let stop = Button::from_icon_name("media-playback-stop-symbolic");
+        let service_clone = service.clone();
+        stop.connect_clicked(move |_| {
+            service_clone.stop(id);
+        });
+        row.append(&stop);
+
+        list.append(&row);
+    }
+}
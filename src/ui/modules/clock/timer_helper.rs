@@ -0,0 +1,221 @@
+// Countdown, stopwatch and pomodoro timers
+// Times are wall clock unix seconds so running timers keep counting while the bar is closed
+use serde::{Deserialize, Serialize};
+
+use crate::config::config::TimerConfig;
+
+pub const TIMERS_STATE_FILE: &str = "timers.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerKind {
+    Countdown,
+    Stopwatch,
+    Pomodoro,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PomodoroPhase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PomodoroPhase::Work => "Work",
+            PomodoroPhase::ShortBreak => "Short break",
+            PomodoroPhase::LongBreak => "Long break",
+        }
+    }
+
+    // seconds
+    fn duration(&self, config: &TimerConfig) -> u64 {
+        let minutes = match self {
+            PomodoroPhase::Work => config.pomodoro_work,
+            PomodoroPhase::ShortBreak => config.pomodoro_short_break,
+            PomodoroPhase::LongBreak => config.pomodoro_long_break,
+        };
+        minutes.max(1) as u64 * 60
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timer {
+    pub id: u32,
+    pub kind: TimerKind,
+    pub label: String,
+    pub duration: u64,           // seconds, 0 for a stopwatch
+    pub elapsed: u64,            // seconds counted before started_at
+    pub started_at: Option<i64>, // None while paused
+    pub phase: PomodoroPhase,
+    pub rounds: u32,             // finished pomodoro work phases
+}
+
+impl Timer {
+    pub fn countdown(id: u32, duration: u64, label: String, now: i64) -> Self {
+        Self {
+            id,
+            kind: TimerKind::Countdown,
+            label,
+            duration,
+            elapsed: 0,
+            started_at: Some(now),
+            phase: PomodoroPhase::Work,
+            rounds: 0,
+        }
+    }
+
+    pub fn stopwatch(id: u32, label: String, now: i64) -> Self {
+        Self { kind: TimerKind::Stopwatch, duration: 0, ..Self::countdown(id, 0, label, now) }
+    }
+
+    pub fn pomodoro(id: u32, config: &TimerConfig, now: i64) -> Self {
+        let phase = PomodoroPhase::Work;
+        Self {
+            kind: TimerKind::Pomodoro,
+            ..Self::countdown(id, phase.duration(config), "Pomodoro".to_string(), now)
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    pub fn elapsed(&self, now: i64) -> u64 {
+        let running = self.started_at.map(|start| (now - start).max(0) as u64).unwrap_or(0);
+        self.elapsed + running
+    }
+
+    // None for a stopwatch
+    pub fn remaining(&self, now: i64) -> Option<u64> {
+        match self.kind {
+            TimerKind::Stopwatch => None,
+            _ => Some(self.duration.saturating_sub(self.elapsed(now))),
+        }
+    }
+
+    pub fn is_finished(&self, now: i64) -> bool {
+        self.remaining(now) == Some(0)
+    }
+
+    pub fn pause(&mut self, now: i64) {
+        self.elapsed = self.elapsed(now);
+        self.started_at = None;
+    }
+
+    pub fn resume(&mut self, now: i64) {
+        if self.started_at.is_none() {
+            self.started_at = Some(now);
+        }
+    }
+
+    // Move a finished pomodoro to its next phase, started when the last one ended
+    pub fn next_phase(&mut self, config: &TimerConfig, now: i64) {
+        let ended_at = self.started_at.map(|start| start + self.duration.saturating_sub(self.elapsed) as i64);
+        self.phase = match self.phase {
+            PomodoroPhase::Work => {
+                self.rounds += 1;
+                if self.rounds.is_multiple_of(config.pomodoro_rounds.max(1)) {
+                    PomodoroPhase::LongBreak
+                } else {
+                    PomodoroPhase::ShortBreak
+                }
+            }
+            _ => PomodoroPhase::Work,
+        };
+        self.duration = self.phase.duration(config);
+        self.elapsed = 0;
+        self.started_at = Some(ended_at.unwrap_or(now).min(now));
+    }
+
+    pub fn title(&self) -> String {
+        match self.kind {
+            TimerKind::Pomodoro => format!("{} {}", self.phase.name(), self.rounds + 1),
+            _ if !self.label.is_empty() => self.label.clone(),
+            TimerKind::Countdown => format_duration(self.duration),
+            TimerKind::Stopwatch => "Stopwatch".to_string(),
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self.kind {
+            TimerKind::Countdown => "\u{f051f}",
+            TimerKind::Stopwatch => "\u{f051b}",
+            TimerKind::Pomodoro => "\u{f025a}",
+        }
+    }
+
+    // Remaining time, or elapsed time for a stopwatch
+    pub fn display_time(&self, now: i64) -> String {
+        format_duration(self.remaining(now).unwrap_or_else(|| self.elapsed(now)))
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimerStore {
+    pub next_id: u32,
+    pub timers: Vec<Timer>,
+}
+
+impl TimerStore {
+    pub fn new_id(&mut self) -> u32 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    // Running timer shown in the bar, the one ending first
+    pub fn shown(&self, now: i64) -> Option<&Timer> {
+        self.timers
+            .iter()
+            .filter(|timer| timer.is_running())
+            .min_by_key(|timer| timer.remaining(now).unwrap_or(u64::MAX))
+    }
+}
+
+// "25m", "1h30m", "90s", a bare number is minutes
+pub fn parse_duration(text: &str) -> Option<u64> {
+    if let Ok(minutes) = text.parse::<u64>() {
+        return Some(minutes * 60);
+    }
+
+    let mut total = 0;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value: u64 = number.parse().ok()?;
+        number.clear();
+        total += match c {
+            'h' => value * 3600,
+            'm' => value * 60,
+            's' => value,
+            _ => return None,
+        };
+    }
+
+    (number.is_empty() && total > 0).then_some(total)
+}
+
+// "05:00", "1:30:00"
+pub fn format_duration(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+pub fn format_timer(format: &str, timer: &Timer, count: usize, now: i64) -> String {
+    format
+        .replace("{icon}", timer.icon())
+        .replace("{remaining}", &timer.display_time(now))
+        .replace("{label}", &timer.title())
+        .replace("{count}", &count.to_string())
+}
@@ -1,3 +1,4 @@
+use std::fs;
 use std::time::Instant;
 
 use lazy_static::lazy_static;
@@ -34,9 +35,54 @@ impl CpuStats {
     fn active(&self) -> u64 {
         self.total() - self.idle - self.iowait
     }
+
+    // Usage in percent since `previous`
+    fn usage_since(&self, previous: &CpuStats) -> f64 {
+        let total_delta = self.total().saturating_sub(previous.total());
+        let active_delta = self.active().saturating_sub(previous.active());
+
+        if total_delta == 0 {
+            return 0.0;
+        }
+        ((active_delta as f64 / total_delta as f64) * 100.0).clamp(0.0, 100.0)
+    }
+}
+
+// Frequencies in MHz of the cores reporting cpufreq
+#[derive(Debug, Clone, Default)]
+pub struct CpuFrequency {
+    pub cores: Vec<(u32, f64)>, // cpu number and current frequency
+    pub avg: f64,
+    pub min: f64,               // lowest cpuinfo_min_freq, lowest current one without it
+    pub max: f64,               // highest cpuinfo_max_freq, highest current one without it
+}
+
+impl CpuFrequency {
+    // Current frequency of cpu `index`
+    pub fn core(&self, index: u32) -> Option<f64> {
+        self.cores.iter().find(|(i, _)| *i == index).map(|(_, mhz)| *mhz)
+    }
 }
+
+#[derive(Debug, Clone, Default)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+// One reading of the cpu, usage is 0 on the first sample
+#[derive(Debug, Clone, Default)]
+pub struct CpuSample {
+    pub usage: f64,
+    pub cores: Vec<(u32, f64)>, // cpu number and usage of each online core
+    pub frequency: Option<CpuFrequency>,
+    pub load: Option<LoadAverage>,
+}
+
 pub struct CpuMonitor {
     previous_stats: Option<CpuStats>,
+    previous_cores: Vec<(u32, CpuStats)>,
     last_update: Instant,
     error_count: u32,
 }
@@ -45,28 +91,22 @@ impl CpuMonitor {
     pub fn new() -> Self {
         Self {
             previous_stats: None,
+            previous_cores: Vec::new(),
             last_update: Instant::now(),
             error_count: 0,
         }
     }
 
-    fn parse_proc_stat() -> Result<CpuStats, String> {
-        LOG.debug("Parsed /proc/stat");
-        
-        let content = std::fs::read_to_string("/proc/stat")
-        .map_err(|e| format!("Failed to read /proc/stat: {}", e))?;
-    
-        let first_line = content.lines().next().ok_or("Empty /proc/stat file")?;
-        
-        let values: Result<Vec<u64>, _> = first_line
+    fn parse_stat_line(line: &str) -> Result<CpuStats, String> {
+        let values: Result<Vec<u64>, _> = line
             .split_whitespace()
             .skip(1)
             .take(8)
             .map(|s| s.parse::<u64>())
             .collect();
-        
+
         let values = values.map_err(|e| format!("Failed to parse CPU values: {}", e))?;
-        
+
         if values.len() < 4 {
             LOG.debug("Not enough CPU stats in /proc/stat");
             return Err("Not enough CPU stats in /proc/stat".to_string());
@@ -87,31 +127,110 @@ impl CpuMonitor {
         })
     }
 
-    pub fn get_cpu_usage(&mut self) -> Result<f64, String> {
-        let current_stats = Self::parse_proc_stat()?;
-        let now = Instant::now();
+    // Aggregate `cpu` line and the `cpuN` line of every online core
+    fn parse_proc_stat() -> Result<(CpuStats, Vec<(u32, CpuStats)>), String> {
+        LOG.debug("Parsed /proc/stat");
 
-        self.error_count = 0;
+        let content = fs::read_to_string("/proc/stat")
+            .map_err(|e| format!("Failed to read /proc/stat: {}", e))?;
+
+        let mut lines = content.lines();
+        let first_line = lines.next().ok_or("Empty /proc/stat file")?;
+        let total = Self::parse_stat_line(first_line)?;
+
+        let cores = lines
+            .take_while(|line| line.starts_with("cpu"))
+            .map(|line| {
+                let index = line
+                    .split_whitespace()
+                    .next()
+                    .and_then(|name| name.strip_prefix("cpu"))
+                    .and_then(|index| index.parse::<u32>().ok())
+                    .ok_or_else(|| format!("Invalid cpu line in /proc/stat: {}", line))?;
+                Ok((index, Self::parse_stat_line(line)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok((total, cores))
+    }
+
+    // Current frequency of every core from /sys/devices/system/cpu/cpuN/cpufreq
+    fn read_frequency() -> Option<CpuFrequency> {
+        let read_mhz = |path: std::path::PathBuf| -> Option<f64> {
+            Some(fs::read_to_string(path).ok()?.trim().parse::<f64>().ok()? / 1000.0)
+        };
+
+        let mut cores = Vec::new();
+        let mut limits = Vec::new();
+        for entry in fs::read_dir("/sys/devices/system/cpu").ok()?.flatten() {
+            let Some(index) = entry.file_name().to_str().and_then(|name| name.strip_prefix("cpu")?.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            let cpufreq = entry.path().join("cpufreq");
+            let Some(current) = read_mhz(cpufreq.join("scaling_cur_freq")) else {
+                continue;
+            };
+            cores.push((index, current));
+            limits.push((read_mhz(cpufreq.join("cpuinfo_min_freq")), read_mhz(cpufreq.join("cpuinfo_max_freq"))));
+        }
+
+        if cores.is_empty() {
+            return None;
+        }
+        cores.sort_by_key(|(index, _)| *index);
+
+        let current = || cores.iter().map(|(_, mhz)| *mhz);
+        let min = limits.iter().filter_map(|(min, _)| *min).reduce(f64::min);
+        let max = limits.iter().filter_map(|(_, max)| *max).reduce(f64::max);
+        Some(CpuFrequency {
+            avg: current().sum::<f64>() / cores.len() as f64,
+            min: min.unwrap_or_else(|| current().fold(f64::MAX, f64::min)),
+            max: max.unwrap_or_else(|| current().fold(0.0, f64::max)),
+            cores,
+        })
+    }
 
-        if let Some(prev_stats) = &self.previous_stats {
-            // Calculate deltas
-            let total_delta = current_stats.total().saturating_sub(prev_stats.total());
-            let active_delta = current_stats.active().saturating_sub(prev_stats.active());
+    fn read_load_average() -> Option<LoadAverage> {
+        let content = fs::read_to_string("/proc/loadavg").ok()?;
+        let mut values = content.split_whitespace().map(|v| v.parse::<f64>().ok());
+        Some(LoadAverage {
+            one: values.next()??,
+            five: values.next()??,
+            fifteen: values.next()??,
+        })
+    }
 
-            if total_delta > 0 {
-                let cpu_usage = (active_delta as f64 / total_delta as f64) * 100.0;
+    pub fn sample(&mut self) -> Result<CpuSample, String> {
+        let (current_stats, current_cores) = Self::parse_proc_stat()?;
+        self.error_count = 0;
 
-                let cpu_usage = cpu_usage.max(0.0).min(100.0);
+        let usage = match &self.previous_stats {
+            Some(prev_stats) => current_stats.usage_since(prev_stats),
+            None => 0.0,
+        };
+
+        // cores can go offline, compare each with the previous sample of the same cpu
+        let cores = current_cores
+            .iter()
+            .map(|(index, core)| match self.previous_cores.iter().find(|(i, _)| i == index) {
+                Some((_, prev)) => (*index, core.usage_since(prev)),
+                None => (*index, 0.0),
+            })
+            .collect();
 
-                self.previous_stats = Some(current_stats);
-                self.last_update = now;
-                return Ok(cpu_usage);
-            }
-        }
         self.previous_stats = Some(current_stats);
-        self.last_update = now;
-        Ok(0.0)
+        self.previous_cores = current_cores;
+        self.last_update = Instant::now();
+
+        Ok(CpuSample {
+            usage,
+            cores,
+            frequency: Self::read_frequency(),
+            load: Self::read_load_average(),
+        })
     }
+
     pub fn handle_error(&mut self, error: String) -> f64 {
         self.error_count += 1;
         eprintln!("CPU monitoring error #{}: {}", self.error_count, error);
@@ -1,44 +1,222 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
 use std::time::Duration;
 
-use gtk::{prelude::WidgetExt, Label};
+use gtk::{cairo, prelude::*, Align, Box as GtkBox, DrawingArea, Label, Orientation, ProgressBar};
+use crate::config::config::CpuConfig;
 use crate::config::config_helper::get_config;
-use crate::ui::modules::cpu::cpu::CpuMonitor;
+use crate::ui::modules::cpu::cpu::{CpuMonitor, CpuSample};
 
-pub fn create_cpu_widget() -> Label {
+pub fn create_cpu_widget() -> GtkBox {
+    let container = GtkBox::new(Orientation::Horizontal, 4);
     let label = Label::new(Some("CPU: 0%"));
     let config = get_config().unwrap();
+    let cpu_config = config.cpu.clone();
 
-    let label_clone = label.clone();
+    let display = CpuDisplay::new(&cpu_config);
+    match &display {
+        CpuDisplay::Text => container.append(&label),
+        CpuDisplay::Bars(bars) => container.append(bars),
+        CpuDisplay::Graph(graph, _) => container.append(graph),
+    }
+
+    let container_clone = container.clone();
     let mut cpu_monitor = CpuMonitor::new();
 
-    glib::timeout_add_local(Duration::from_secs(1), move || {
+    glib::timeout_add_local(Duration::from_secs(cpu_config.interval.max(1) as u64), move || {
         // Only update if module is visible
         if !config.modules.cpu{
             return glib::ControlFlow::Continue;
         }
 
-        let usage = match cpu_monitor.get_cpu_usage() {
-            Ok(usage) => usage,
-            Err(err) => cpu_monitor.handle_error(err),
+        let sample = match cpu_monitor.sample() {
+            Ok(sample) => sample,
+            Err(err) => CpuSample {
+                usage: cpu_monitor.handle_error(err),
+                ..Default::default()
+            },
         };
 
-        label_clone.set_text(&format!("CPU: {:.1}%", usage as i64));
+        label.set_text(&format_cpu(&cpu_config.format, &sample));
+        display.update(&sample, &cpu_config);
+        if cpu_config.tooltip {
+            container_clone.set_tooltip_markup(Some(&format_cpu(&cpu_config.tooltip_format, &sample)));
+        }
 
         // Remove classes for conflict
-        label_clone.remove_css_class("low");
-        label_clone.remove_css_class("medium");
-        label_clone.remove_css_class("high");
+        container_clone.remove_css_class("low");
+        container_clone.remove_css_class("medium");
+        container_clone.remove_css_class("high");
 
         // Classes for styling 
-        let css_class = match usage {
-            u if u < 30.0 => "low",
-            u if u < 70.0 => "medium",
-            _ => "high",
-        };
-        label_clone.add_css_class(css_class);
+        container_clone.add_css_class(load_class(sample.usage, &cpu_config));
 
         glib::ControlFlow::Continue
     });
 
-    label
-}
\ No newline at end of file
+    container
+}
+
+// Graphical cpu modes, text only uses the label
+enum CpuDisplay {
+    Text,
+    Bars(GtkBox),                                   // one vertical #cpu-bar per core
+    Graph(DrawingArea, Rc<RefCell<VecDeque<f64>>>), // usage history, newest last
+}
+
+impl CpuDisplay {
+    fn new(config: &CpuConfig) -> Self {
+        match config.display.as_str() {
+            "bars" => {
+                let bars = GtkBox::new(Orientation::Horizontal, 1);
+                bars.set_widget_name("cpu-bars");
+                Self::Bars(bars)
+            }
+            "graph" => {
+                let history = Rc::new(RefCell::new(VecDeque::with_capacity(config.graph_length)));
+                let graph = DrawingArea::new();
+                graph.set_widget_name("cpu-graph");
+                graph.set_content_width(config.graph_width);
+                graph.set_valign(Align::Fill);
+
+                let history_clone = history.clone();
+                let config = config.clone();
+                graph.set_draw_func(move |_, cr, width, height| {
+                    draw_graph(cr, width as f64, height as f64, &history_clone.borrow(), &config);
+                });
+                Self::Graph(graph, history)
+            }
+            _ => Self::Text,
+        }
+    }
+
+    fn update(&self, sample: &CpuSample, config: &CpuConfig) {
+        match self {
+            Self::Text => {}
+            Self::Bars(bars) => {
+                // cores can go offline, create the bars again if the count changed
+                let mut count = 0;
+                let mut child = bars.first_child();
+                while let Some(widget) = child {
+                    count += 1;
+                    child = widget.next_sibling();
+                }
+                if count != sample.cores.len() {
+                    while let Some(child) = bars.first_child() {
+                        bars.remove(&child);
+                    }
+                    for _ in &sample.cores {
+                        let bar = ProgressBar::new();
+                        bar.set_widget_name("cpu-bar");
+                        bar.set_orientation(Orientation::Vertical);
+                        bar.set_inverted(true);
+                        bars.append(&bar);
+                    }
+                }
+
+                let mut child = bars.first_child();
+                for (_, usage) in &sample.cores {
+                    let Some(bar) = child.and_then(|c| c.downcast::<ProgressBar>().ok()) else {
+                        break;
+                    };
+                    bar.set_fraction(usage / 100.0);
+                    bar.remove_css_class("low");
+                    bar.remove_css_class("medium");
+                    bar.remove_css_class("high");
+                    bar.add_css_class(load_class(*usage, config));
+                    child = bar.next_sibling();
+                }
+            }
+            Self::Graph(graph, history) => {
+                let mut history = history.borrow_mut();
+                history.push_back(sample.usage);
+                while history.len() > config.graph_length.max(2) {
+                    history.pop_front();
+                }
+                graph.queue_draw();
+            }
+        }
+    }
+}
+
+// Filled usage history, colored by the latest load
+fn draw_graph(cr: &cairo::Context, width: f64, height: f64, history: &VecDeque<f64>, config: &CpuConfig) {
+    let Some(&latest) = history.back() else {
+        return;
+    };
+
+    // same colors as the #cpu-bar gradient
+    let (r, g, b) = match load_class(latest, config) {
+        "low" => (0.651, 0.890, 0.631),
+        "medium" => (0.976, 0.886, 0.686),
+        _ => (0.953, 0.545, 0.659),
+    };
+
+    let step = width / (config.graph_length.max(2) - 1) as f64;
+    let start = width - step * (history.len() - 1) as f64;
+
+    cr.move_to(start, height);
+    for (i, usage) in history.iter().enumerate() {
+        cr.line_to(start + step * i as f64, height - height * usage / 100.0);
+    }
+    cr.line_to(width, height);
+    cr.close_path();
+
+    cr.set_source_rgba(r, g, b, 0.4);
+    let _ = cr.fill_preserve();
+    cr.set_source_rgb(r, g, b);
+    cr.set_line_width(1.0);
+    let _ = cr.stroke();
+}
+
+// css class for a usage with the thresholds from config
+pub fn load_class(usage: f64, config: &CpuConfig) -> &'static str {
+    match usage {
+        u if u < config.medium_threshold => "low",
+        u if u < config.high_threshold => "medium",
+        _ => "high",
+    }
+}
+
+// Replace cpu placeholders, see CpuConfig
+pub fn format_cpu(format: &str, sample: &CpuSample) -> String {
+    let mut text = format.to_string();
+
+    for (i, usage) in &sample.cores {
+        text = text.replace(&format!("{{usage{}}}", i), &format!("{:.0}", usage));
+    }
+
+    let ghz = |mhz: Option<f64>| match mhz {
+        Some(mhz) => format!("{:.1}", mhz / 1000.0),
+        None => "-".to_string(),
+    };
+    let frequency = sample.frequency.as_ref();
+    let load = |value: Option<f64>| match value {
+        Some(value) => format!("{:.2}", value),
+        None => "-".to_string(),
+    };
+
+    if text.contains("{cores}") {
+        let cores: Vec<String> = sample
+            .cores
+            .iter()
+            .map(|(i, usage)| {
+                let freq = frequency.and_then(|f| f.core(*i));
+                match freq {
+                    Some(_) => format!("Core {}: {:.0}% {} GHz", i, usage, ghz(freq)),
+                    None => format!("Core {}: {:.0}%", i, usage),
+                }
+            })
+            .collect();
+        text = text.replace("{cores}", &cores.join("\n"));
+    }
+
+    text.replace("{usage}", &format!("{:.0}", sample.usage))
+        .replace("{freq_avg}", &ghz(frequency.map(|f| f.avg)))
+        .replace("{freq_min}", &ghz(frequency.map(|f| f.min)))
+        .replace("{freq_max}", &ghz(frequency.map(|f| f.max)))
+        .replace("{load1}", &load(sample.load.as_ref().map(|l| l.one)))
+        .replace("{load5}", &load(sample.load.as_ref().map(|l| l.five)))
+        .replace("{load15}", &load(sample.load.as_ref().map(|l| l.fifteen)))
+}
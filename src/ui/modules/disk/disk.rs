@@ -0,0 +1,111 @@
+/* DISK MODULE */
+
+use gtk::{Box, Label, Orientation, glib, prelude::*};
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::config::config::DiskConfig;
+use crate::ui::modules::disk::disk_helper::{
+    format_disk, get_disk_usage, get_mount_device, read_diskstats, usage_class, DiskIo,
+};
+
+pub struct Disk {
+    widget: Box,
+    labels: Vec<(String, Label)>, // mount point and its label
+    config: DiskConfig,
+}
+
+impl Disk {
+    pub fn new(config: DiskConfig) -> Self {
+        let disk_box = Box::new(Orientation::Horizontal, 8);
+        disk_box.set_widget_name("disk");
+        disk_box.add_css_class("modules");
+
+        let labels = config
+            .mounts
+            .iter()
+            .map(|mount| {
+                let label = Label::new(None);
+                disk_box.append(&label);
+                (mount.clone(), label)
+            })
+            .collect();
+
+        Self {
+            widget: disk_box,
+            labels,
+            config,
+        }
+    }
+
+    pub fn widget(&self) -> &Box {
+        &self.widget
+    }
+
+    pub fn start_updates(self) {
+        let config = self.config;
+        let labels = self.labels;
+        let interval = config.interval.max(1);
+
+        // device of each mount for io, looked up once
+        let devices: HashMap<String, String> = if config.io {
+            labels
+                .iter()
+                .filter_map(|(mount, _)| Some((mount.clone(), get_mount_device(mount)?)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        let read_stats = move || if config.io { read_diskstats() } else { HashMap::new() };
+        let mut previous = (Instant::now(), read_stats());
+
+        let mut update = move || {
+            let stats = read_stats();
+            let now = Instant::now();
+            let elapsed = now.duration_since(previous.0).as_secs_f64().max(1.0);
+
+            for (mount, label) in &labels {
+                let Some(usage) = get_disk_usage(mount) else {
+                    label.set_text(&format!("{} N/A", mount));
+                    continue;
+                };
+
+                let io = devices.get(mount).and_then(|device| {
+                    let (read, written) = stats.get(device)?;
+                    let (prev_read, prev_written) = previous.1.get(device)?;
+                    Some(DiskIo {
+                        read: ((read.saturating_sub(*prev_read) * 512) as f64 / elapsed) as u64,
+                        write: ((written.saturating_sub(*prev_written) * 512) as f64 / elapsed) as u64,
+                    })
+                });
+
+                let format = config.formats.get(mount).unwrap_or(&config.format);
+                label.set_text(&format_disk(format, mount, &usage, io.as_ref()));
+                if config.tooltip {
+                    label.set_tooltip_markup(Some(&format_disk(
+                        &config.tooltip_format,
+                        &glib::markup_escape_text(mount),
+                        &usage,
+                        io.as_ref(),
+                    )));
+                }
+
+                label.remove_css_class("warning");
+                label.remove_css_class("critical");
+                if let Some(class) = usage_class(usage.percentage(), &config) {
+                    label.add_css_class(class);
+                }
+            }
+
+            if config.io {
+                previous = (now, stats);
+            }
+        };
+
+        update();
+        glib::timeout_add_seconds_local(interval, move || {
+            update();
+            glib::ControlFlow::Continue
+        });
+    }
+}
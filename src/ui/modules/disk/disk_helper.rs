@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::fs;
+
+use crate::config::config::DiskConfig;
+use crate::utils::format::{format_rate, format_size};
+
+// Space of a mounted filesystem in bytes
+#[derive(Debug, Clone, Default)]
+pub struct DiskUsage {
+    pub total: u64,
+    pub free: u64, // available to unprivileged users, as shown by df
+    pub used: u64,
+}
+
+impl DiskUsage {
+    pub fn percentage(&self) -> f64 {
+        let size = self.used + self.free;
+        if size == 0 {
+            return 0.0;
+        }
+        self.used as f64 / size as f64 * 100.0
+    }
+}
+
+// Bytes per second since the last update
+#[derive(Debug, Clone, Default)]
+pub struct DiskIo {
+    pub read: u64,
+    pub write: u64,
+}
+
+pub fn get_disk_usage(mount: &str) -> Option<DiskUsage> {
+    let path = CString::new(mount).ok()?;
+    // SAFETY: statvfs only writes into the zeroed struct we pass
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let block_size = stat.f_frsize as u64;
+    let total = stat.f_blocks as u64 * block_size;
+    let free_blocks = stat.f_bfree as u64 * block_size;
+    Some(DiskUsage {
+        total,
+        free: stat.f_bavail as u64 * block_size,
+        used: total.saturating_sub(free_blocks),
+    })
+}
+
+// Block device of a mount point from /proc/self/mounts, eg: "/" -> "nvme0n1p2"
+pub fn get_mount_device(mount: &str) -> Option<String> {
+    let mounts = fs::read_to_string("/proc/self/mounts").ok()?;
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some((parts.next()?, parts.next()?))
+        })
+        .filter(|(_, mount_point)| *mount_point == mount)
+        .filter_map(|(source, _)| source.strip_prefix("/dev/"))
+        .next_back()
+        .map(|device| {
+            // /dev/mapper/root is listed as dm-N in diskstats
+            fs::canonicalize(format!("/dev/{}", device))
+                .ok()
+                .and_then(|path| path.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .unwrap_or_else(|| device.to_string())
+        })
+}
+
+// Sectors read and written of every device, sectors are always 512 bytes here
+pub fn read_diskstats() -> HashMap<String, (u64, u64)> {
+    let Ok(content) = fs::read_to_string("/proc/diskstats") else {
+        return HashMap::new();
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let name = parts.get(2)?;
+            let read = parts.get(5)?.parse::<u64>().ok()?;
+            let written = parts.get(9)?.parse::<u64>().ok()?;
+            Some((name.to_string(), (read, written)))
+        })
+        .collect()
+}
+
+pub fn usage_class(percentage: f64, config: &DiskConfig) -> Option<&'static str> {
+    match percentage {
+        p if p >= config.critical_threshold => Some("critical"),
+        p if p >= config.warning_threshold => Some("warning"),
+        _ => None,
+    }
+}
+
+// Replace disk placeholders, see DiskConfig
+pub fn format_disk(format: &str, mount: &str, usage: &DiskUsage, io: Option<&DiskIo>) -> String {
+    let io = io.cloned().unwrap_or_default();
+    format
+        .replace("{mount}", mount)
+        .replace("{used}", &format_size(usage.used))
+        .replace("{free}", &format_size(usage.free))
+        .replace("{total}", &format_size(usage.total))
+        .replace("{percentage}", &format!("{:.0}", usage.percentage()))
+        .replace("{read}", &format_rate(io.read))
+        .replace("{write}", &format_rate(io.write))
+}
@@ -0,0 +1,2 @@
+pub mod disk;
+pub mod disk_helper;
\ No newline at end of file
@@ -0,0 +1,78 @@
+/* FAN MODULE */
+
+use gtk::{Label, glib, prelude::*};
+
+use crate::config::config::{FanConfig, TemperatureConfig};
+use crate::ui::modules::fan::fan_helper::{
+    configured_fan, fastest_fan, format_fan_text, format_fan_tooltip,
+};
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("fan",LogLevel::Debug);
+}
+
+pub struct Fan {
+    label: Label,
+    config: FanConfig,
+    temperature_config: TemperatureConfig, // unit of the temperatures in the tooltip
+}
+
+impl Fan {
+    pub fn new(config: FanConfig, temperature_config: TemperatureConfig) -> Self {
+        let label = Label::new(None);
+        label.set_widget_name("fan");
+        label.add_css_class("modules");
+
+        Self { label, config, temperature_config }
+    }
+
+    pub fn widget(&self) -> &Label {
+        &self.label
+    }
+
+    pub fn start_updates(self) {
+        let label = self.label.clone();
+        let config = self.config;
+        let temperature_config = self.temperature_config;
+        let interval = config.interval.max(1);
+        // a typo in chip or label should not silently show another fan
+        let configured = !config.chip.is_empty() || !config.label.is_empty();
+        let fan = configured_fan(&config);
+        if configured && fan.is_none() {
+            LOG.error(&format!("No fan matching chip '{}' label '{}'", config.chip, config.label));
+        }
+
+        let update = move || {
+            let reading = match fan {
+                Some(fan) => fan.read().map(|rpm| (fan, rpm)),
+                None if configured => None,
+                None => fastest_fan(),
+            };
+
+            match reading {
+                Some((fan, rpm)) => {
+                    label.set_text(&format_fan_text(fan, rpm, &config));
+                    // stopped fans of zero rpm mode
+                    if rpm == 0.0 {
+                        label.add_css_class("stopped");
+                    } else {
+                        label.remove_css_class("stopped");
+                    }
+                }
+                None => label.set_text("No fan"),
+            }
+
+            if config.tooltip {
+                label.set_tooltip_markup(Some(&format_fan_tooltip(&temperature_config)));
+            }
+        };
+
+        update();
+        glib::timeout_add_seconds_local(interval, move || {
+            update();
+            glib::ControlFlow::Continue
+        });
+    }
+}
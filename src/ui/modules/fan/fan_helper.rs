@@ -0,0 +1,83 @@
+use crate::config::config::{FanConfig, TemperatureConfig};
+use crate::system::hwmon::{find_sensor, sensors, Sensor, SensorKind};
+use crate::ui::modules::temperature::temperature_helper::{to_unit, unit};
+
+// Fan from config, None when it is not found or when neither chip nor label is set
+pub fn configured_fan(config: &FanConfig) -> Option<&'static Sensor> {
+    if config.chip.is_empty() && config.label.is_empty() {
+        return None;
+    }
+    find_sensor(SensorKind::Fan, &config.chip, &config.label)
+}
+
+// Fan with the highest speed right now and its rpm
+pub fn fastest_fan() -> Option<(&'static Sensor, f64)> {
+    sensors()
+        .iter()
+        .filter(|s| s.kind == SensorKind::Fan)
+        .filter_map(|s| Some((s, s.read()?)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+// pwmN controlling fanN of the same chip
+pub fn fan_pwm(fan: &Sensor) -> Option<f64> {
+    let name = fan.input.file_name()?.to_str()?;
+    let index = name.strip_prefix("fan")?.strip_suffix("_input")?;
+    let pwm_path = fan.input.with_file_name(format!("pwm{}", index));
+
+    sensors()
+        .iter()
+        .find(|s| s.kind == SensorKind::Pwm && s.input == pwm_path)?
+        .read()
+}
+
+// Format main widget text
+pub fn format_fan_text(fan: &Sensor, rpm: f64, config: &FanConfig) -> String {
+    let pwm = match fan_pwm(fan) {
+        Some(pwm) => format!("{:.0}", pwm),
+        None => "-".to_string(),
+    };
+    config
+        .format
+        .replace("{icon}", "\u{f0210}")
+        .replace("{rpm}", &format!("{:.0}", rpm))
+        .replace("{pwm}", &pwm)
+        .replace("{chip}", &fan.chip)
+        .replace("{label}", &fan.label)
+        .trim()
+        .to_string()
+}
+
+// Format tooltip, a table of every fan and temperature sensor in the temperature module's unit
+pub fn format_fan_tooltip(temperature_config: &TemperatureConfig) -> String {
+    let mut fans = Vec::new();
+    let mut temperatures = Vec::new();
+
+    for sensor in sensors() {
+        let Some(value) = sensor.read() else {
+            continue;
+        };
+        let name = format!("{} {}", sensor.chip, sensor.label);
+        match sensor.kind {
+            SensorKind::Fan => match fan_pwm(sensor) {
+                Some(pwm) => fans.push(format!("{:<24} {:>5.0} RPM {:>4.0}%", name, value, pwm)),
+                None => fans.push(format!("{:<24} {:>5.0} RPM", name, value)),
+            },
+            SensorKind::Temperature => temperatures.push(format!(
+                "{:<24} {:>5.0} {}",
+                name,
+                to_unit(value, temperature_config),
+                unit(temperature_config)
+            )),
+            SensorKind::Pwm => {}
+        }
+    }
+
+    let mut tooltip = String::from("<b>Fans</b>\n");
+    tooltip.push_str(&format!("<tt>{}</tt>", glib::markup_escape_text(&fans.join("\n"))));
+    if !temperatures.is_empty() {
+        tooltip.push_str("\n<b>Temperatures</b>\n");
+        tooltip.push_str(&format!("<tt>{}</tt>", glib::markup_escape_text(&temperatures.join("\n"))));
+    }
+    tooltip
+}
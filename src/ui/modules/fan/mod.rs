@@ -0,0 +1,2 @@
+pub mod fan;
+pub mod fan_helper;
\ No newline at end of file
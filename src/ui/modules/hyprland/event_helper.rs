@@ -0,0 +1,100 @@
+// Shared hyprland event listener thread and GLib side of the event channel
+use async_channel::{Receiver, Sender};
+use gtk::{glib, prelude::*};
+use hyprland::event_listener::EventListener;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("hyprland_events",LogLevel::Debug);
+}
+
+const MIN_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+// listener that ran this long was connected, start backoff again from the minimum
+const STABLE_CONNECTION: Duration = Duration::from_secs(10);
+
+// Run a hyprland event listener in its own thread
+// `setup` adds the handlers and is called again on every reconnect,
+// `reconnect_event` is sent after reconnecting so the widget can refresh
+// missed state. The thread stops once the receiver is dropped.
+pub fn spawn_event_listener<T, F>(name: &'static str, tx: Sender<T>, reconnect_event: T, setup: F)
+where
+    T: Clone + Send + 'static,
+    F: Fn(&mut EventListener, &Sender<T>) + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut backoff = MIN_BACKOFF;
+        let mut reconnecting = false;
+
+        while !tx.is_closed() {
+            if reconnecting && tx.send_blocking(reconnect_event.clone()).is_err() {
+                break;
+            }
+
+            let mut event_listener = EventListener::new();
+            setup(&mut event_listener, &tx);
+
+            let started = Instant::now();
+            match event_listener.start_listener() {
+                Ok(()) => LOG.error(&format!("{}: hyprland closed the event socket", name)),
+                Err(e) => LOG.error(&format!("{}: event listener failed: {}", name, e)),
+            }
+
+            if started.elapsed() >= STABLE_CONNECTION {
+                backoff = MIN_BACKOFF;
+            }
+            LOG.warn(&format!("{}: reconnecting in {:?}", name, backoff));
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            reconnecting = true;
+        }
+
+        LOG.debug(&format!("{}: receiver dropped, stopping event listener", name));
+    });
+}
+
+// Receive events on the GLib main context
+// `on_event` runs for every event as soon as it arrives,
+// `update` runs once on the next frame of `widget` however many events came in before it.
+// Widgets that are not mapped have no frame clock, they are updated right away.
+pub fn attach_events<T, E, U>(widget: &impl IsA<gtk::Widget>, rx: Receiver<T>, mut on_event: E, update: U)
+where
+    T: 'static,
+    E: FnMut(T) + 'static,
+    U: Fn() + 'static,
+{
+    let widget = widget.clone().upcast::<gtk::Widget>();
+    let update = Rc::new(update);
+    let pending = Rc::new(Cell::new(false));
+
+    glib::spawn_future_local(async move {
+        while let Ok(event) = rx.recv().await {
+            on_event(event);
+
+            // update already scheduled for this frame
+            if pending.replace(true) {
+                continue;
+            }
+
+            if widget.is_mapped() {
+                let pending = pending.clone();
+                let update = update.clone();
+                widget.add_tick_callback(move |_, _| {
+                    pending.set(false);
+                    update();
+                    glib::ControlFlow::Break
+                });
+            } else {
+                pending.set(false);
+                update();
+            }
+        }
+        LOG.debug("event channel closed");
+    });
+}
@@ -0,0 +1,23 @@
+use std::collections::HashMap;
+
+use crate::config::config::KeyboardLayoutConfig;
+
+// Short name of a layout, from config or the first two letters
+// "English (US)" -> "en"
+pub fn short_layout_name(layout: &str, short_names: &HashMap<String, String>) -> String {
+    if let Some(short) = short_names.get(layout) {
+        return short.clone();
+    }
+    layout.chars().take(2).collect::<String>().to_lowercase()
+}
+
+// Format keyboard layout text
+// {layout} full name, {short} short name, {keyboard} device name
+pub fn format_layout(format: &str, keyboard: &str, layout: &str, config: &KeyboardLayoutConfig) -> String {
+    format
+        .replace("{layout}", layout)
+        .replace("{short}", &short_layout_name(layout, &config.short_names))
+        .replace("{keyboard}", keyboard)
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,156 @@
+// Hyprland keyboard layout module
+use gtk::{gdk, prelude::*};
+use gtk::{GestureClick, Label};
+use hyprland::ctl::switch_xkb_layout::{self, SwitchXKBLayoutCmdTypes};
+use hyprland::data::Devices;
+use hyprland::shared::HyprData;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::config::config::KeyboardLayoutConfig;
+use crate::ui::modules::hyprland::event_helper::{attach_events, spawn_event_listener};
+use crate::ui::modules::hyprland::keyboard::keyboard_helper::format_layout;
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("keyboard_layout",LogLevel::Debug);
+}
+
+#[derive(Clone)]
+pub struct KeyboardLayoutWidget {
+    label: Label,
+    config: Rc<KeyboardLayoutConfig>,
+    layouts: Rc<RefCell<BTreeMap<String, String>>>, // keyboard name -> active layout
+    keyboard: Rc<RefCell<String>>,                  // keyboard shown on the bar
+}
+
+impl KeyboardLayoutWidget {
+    pub fn new(config: KeyboardLayoutConfig) -> Self {
+        let label = Label::new(None);
+        label.set_widget_name("keyboard-layout");
+        label.add_css_class("modules");
+
+        let widget = Self {
+            label,
+            config: Rc::new(config),
+            layouts: Rc::new(RefCell::new(BTreeMap::new())),
+            keyboard: Rc::new(RefCell::new(String::new())),
+        };
+
+        widget.load_devices();
+        widget.update_label();
+        widget.setup_click();
+        widget.start_event_listener();
+        widget
+    }
+
+    pub fn widget(&self) -> &Label {
+        &self.label
+    }
+
+    // Read layouts of all keyboards, events only report changes
+    fn load_devices(&self) {
+        let devices = match Devices::get() {
+            Ok(devices) => devices,
+            Err(e) => {
+                LOG.error(&format!("Failed to get keyboards: {}", e));
+                return;
+            }
+        };
+
+        let keyboard = if !self.config.keyboard.is_empty() {
+            self.config.keyboard.clone()
+        } else {
+            devices
+                .keyboards
+                .iter()
+                .find(|k| k.main)
+                .or(devices.keyboards.first())
+                .map(|k| k.name.clone())
+                .unwrap_or_default()
+        };
+        *self.keyboard.borrow_mut() = keyboard;
+
+        *self.layouts.borrow_mut() = devices
+            .keyboards
+            .into_iter()
+            .map(|k| (k.name, k.active_keymap))
+            .collect();
+    }
+
+    fn update_label(&self) {
+        let keyboard = self.keyboard.borrow();
+        let layouts = self.layouts.borrow();
+
+        match layouts.get(keyboard.as_str()) {
+            Some(layout) => {
+                self.label
+                    .set_text(&format_layout(&self.config.format, &keyboard, layout, &self.config));
+                self.label.set_visible(true);
+            }
+            None => self.label.set_visible(false),
+        }
+
+        if self.config.tooltip {
+            let tooltip: Vec<String> = layouts
+                .iter()
+                .map(|(name, layout)| format!("{}: {}", name, layout))
+                .collect();
+            self.label.set_tooltip_text(Some(&tooltip.join("\n")));
+        }
+    }
+
+    // Left click switches to the next layout, right click to the previous one
+    fn setup_click(&self) {
+        let gesture = GestureClick::new();
+        gesture.set_button(0);
+        // claim on press so the bar's right click blocker does not cancel the release
+        gesture.connect_pressed(|gesture, _, _, _| {
+            gesture.set_state(gtk::EventSequenceState::Claimed);
+        });
+        let widget = self.clone();
+        gesture.connect_released(move |gesture, _, _, _| {
+            let cmd = match gesture.current_button() {
+                gdk::BUTTON_PRIMARY => SwitchXKBLayoutCmdTypes::Next,
+                gdk::BUTTON_SECONDARY => SwitchXKBLayoutCmdTypes::Previous,
+                _ => return,
+            };
+            let keyboard = widget.keyboard.borrow().clone();
+            if let Err(e) = switch_xkb_layout::call(&keyboard, cmd) {
+                LOG.error(&format!("Failed to switch layout of {}: {}", keyboard, e));
+            }
+        });
+        self.label.add_controller(gesture);
+    }
+
+    fn start_event_listener(&self) {
+        let (tx, rx) = async_channel::unbounded();
+
+        // None reloads all keyboards, layouts may have changed while hyprland was gone
+        spawn_event_listener("keyboard_layout", tx, None, |event_listener, tx| {
+            let tx_clone = tx.clone();
+            event_listener.add_layout_changed_handler(move |event| {
+                let layout = Some((event.keyboard_name, event.layout_name));
+                if let Err(e) = tx_clone.send_blocking(layout) {
+                    LOG.error(&format!("Failed to send layout event: {}", e));
+                }
+            });
+        });
+
+        let widget = self.clone();
+        let update_widget = self.clone();
+        attach_events(
+            &self.label,
+            rx,
+            move |event| match event {
+                Some((keyboard, layout)) => {
+                    widget.layouts.borrow_mut().insert(keyboard, layout);
+                }
+                None => widget.load_devices(),
+            },
+            move || update_widget.update_label(),
+        );
+    }
+}
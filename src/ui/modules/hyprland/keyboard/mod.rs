@@ -0,0 +1,2 @@
+pub mod keyboard_layout;
+pub mod keyboard_helper;
\ No newline at end of file
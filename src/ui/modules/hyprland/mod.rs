@@ -1,2 +1,6 @@
 pub mod workspace;
-pub mod window;
\ No newline at end of file
+pub mod window;
+pub mod taskbar;
+pub mod submap;
+pub mod keyboard;
+pub mod event_helper;
\ No newline at end of file
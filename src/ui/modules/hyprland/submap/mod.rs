@@ -0,0 +1 @@
+pub mod submap;
\ No newline at end of file
@@ -0,0 +1,70 @@
+// Hyprland submap module, shows the active keybind submap
+use gtk::prelude::*;
+use gtk::Label;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::config::config::SubmapConfig;
+use crate::ui::modules::hyprland::event_helper::{attach_events, spawn_event_listener};
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("submap",LogLevel::Debug);
+}
+
+pub struct SubmapWidget {
+    label: Label,
+}
+
+impl SubmapWidget {
+    pub fn new(config: SubmapConfig) -> Self {
+        let label = Label::new(None);
+        label.set_widget_name("submap");
+        label.add_css_class("modules");
+        // hyprland starts in the default submap
+        label.set_visible(false);
+
+        let widget = Self { label };
+        widget.start_event_listener(config);
+        widget
+    }
+
+    pub fn widget(&self) -> &Label {
+        &self.label
+    }
+
+    fn start_event_listener(&self, config: SubmapConfig) {
+        let (tx, rx) = async_channel::unbounded();
+
+        // a restarted hyprland is back in the default submap
+        spawn_event_listener("submap", tx, String::new(), |event_listener, tx| {
+            let tx_clone = tx.clone();
+            event_listener.add_sub_map_changed_handler(move |name| {
+                if let Err(e) = tx_clone.send_blocking(name) {
+                    LOG.error(&format!("Failed to send submap event: {}", e));
+                }
+            });
+        });
+
+        let current = Rc::new(RefCell::new(String::new()));
+        let current_clone = current.clone();
+        let label = self.label.clone();
+        attach_events(
+            &self.label,
+            rx,
+            move |name| *current_clone.borrow_mut() = name,
+            move || {
+                let name = current.borrow();
+                // empty when leaving a submap with `submap, reset`
+                if name.is_empty() || name.as_str() == "default" {
+                    label.set_visible(false);
+                } else {
+                    LOG.debug(&format!("Submap changed to {}", name));
+                    label.set_text(&config.format.replace("{name}", &name));
+                    label.set_visible(true);
+                }
+            },
+        );
+    }
+}
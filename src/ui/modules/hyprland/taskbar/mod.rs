@@ -0,0 +1,2 @@
+pub mod taskbar;
+pub mod taskbar_helper;
\ No newline at end of file
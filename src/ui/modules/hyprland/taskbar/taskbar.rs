@@ -0,0 +1,277 @@
+// Hyprland taskbar module
+use gio::{Menu, SimpleAction, SimpleActionGroup};
+use gtk::pango::EllipsizeMode;
+use gtk::{Box, Button, GestureClick, Image, Label, Orientation, PopoverMenu};
+use gtk::{gdk, glib, prelude::*};
+use hyprland::data::{Client, Clients, Monitors, Workspaces};
+use hyprland::dispatch::{
+    Dispatch, DispatchType, FullscreenType, WindowIdentifier, WorkspaceIdentifierWithSpecial,
+};
+use hyprland::shared::{Address, HyprData, HyprDataActiveOptional, HyprDataVec};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::config::config::TaskbarConfig;
+use crate::ui::modules::hyprland::event_helper::{attach_events, spawn_event_listener};
+use crate::ui::modules::hyprland::taskbar::taskbar_helper::{group_tasks, TaskGroup};
+use crate::ui::modules::hyprland::window::window_helper::get_app_icon;
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("taskbar",LogLevel::Debug);
+}
+
+#[derive(Clone)]
+pub struct TaskbarWidget {
+    container: Box,
+    output: Option<String>, // monitor this bar is on, None shows all
+    config: Rc<TaskbarConfig>,
+    groups: Rc<RefCell<Vec<TaskGroup>>>,
+    active: Rc<RefCell<Option<Address>>>,
+}
+
+impl TaskbarWidget {
+    pub fn new(output: Option<String>, config: TaskbarConfig) -> Self {
+        let container = Box::new(Orientation::Horizontal, 2);
+        container.set_widget_name("taskbar");
+        container.add_css_class("modules");
+
+        let widget = Self {
+            container,
+            output,
+            config: Rc::new(config),
+            groups: Rc::new(RefCell::new(Vec::new())),
+            active: Rc::new(RefCell::new(None)),
+        };
+
+        widget.update_tasks();
+        widget.start_event_listener();
+        widget
+    }
+
+    pub fn widget(&self) -> &Box {
+        &self.container
+    }
+
+    fn start_event_listener(&self) {
+        let (tx, rx) = async_channel::unbounded();
+
+        // every event just triggers a refresh, they are coalesced per frame
+        spawn_event_listener("taskbar", tx, (), |event_listener, tx| {
+            let tx_clone = tx.clone();
+            event_listener.add_window_opened_handler(move |_| send_refresh(&tx_clone));
+
+            let tx_clone = tx.clone();
+            event_listener.add_window_closed_handler(move |_| send_refresh(&tx_clone));
+
+            let tx_clone = tx.clone();
+            event_listener.add_window_moved_handler(move |_| send_refresh(&tx_clone));
+
+            let tx_clone = tx.clone();
+            event_listener.add_active_window_changed_handler(move |_| send_refresh(&tx_clone));
+
+            let tx_clone = tx.clone();
+            event_listener.add_window_title_changed_handler(move |_| send_refresh(&tx_clone));
+        });
+
+        let widget = self.clone();
+        attach_events(&self.container, rx, |_| {}, move || widget.update_tasks());
+    }
+
+    fn update_tasks(&self) {
+        let clients = match Clients::get() {
+            Ok(clients) => clients.to_vec(),
+            Err(e) => {
+                LOG.error(&format!("Failed to get windows: {}", e));
+                return;
+            }
+        };
+        let active = match Client::get_active() {
+            Ok(client) => client.map(|c| c.address),
+            Err(_) => None,
+        };
+
+        let monitor = match (&self.output, self.config.all_outputs) {
+            (Some(output), false) => Monitors::get()
+                .ok()
+                .and_then(|monitors| monitors.into_iter().find(|m| &m.name == output))
+                .map(|m| m.id),
+            _ => None,
+        };
+
+        let groups = group_tasks(&clients, active.as_ref(), monitor, self.config.group_by_app);
+        *self.active.borrow_mut() = active;
+
+        if *self.groups.borrow() == groups {
+            return;
+        }
+
+        while let Some(child) = self.container.first_child() {
+            self.container.remove(&child);
+        }
+        for group in &groups {
+            self.container.append(&self.create_task_button(group));
+        }
+        self.container.set_visible(!groups.is_empty());
+        *self.groups.borrow_mut() = groups;
+    }
+
+    fn create_task_button(&self, group: &TaskGroup) -> Button {
+        let button = Button::new();
+        button.add_css_class("task-button");
+        if group.active {
+            button.add_css_class("active");
+        }
+
+        let content = Box::new(Orientation::Horizontal, 4);
+
+        let icon = Image::new();
+        icon.set_pixel_size(self.config.icon_size);
+        match get_app_icon(&group.class) {
+            Some(gicon) => icon.set_from_gicon(&gicon),
+            None => icon.set_icon_name(Some("application-x-executable")),
+        }
+        content.append(&icon);
+
+        if self.config.show_title {
+            let active = self.active.borrow();
+            let title = Label::new(Some(&group.primary(active.as_ref()).title));
+            if self.config.max_title_length > 0 {
+                title.set_ellipsize(EllipsizeMode::End);
+                title.set_max_width_chars(self.config.max_title_length as i32);
+            }
+            content.append(&title);
+        }
+
+        if group.windows.len() > 1 {
+            let count = Label::new(Some(&group.windows.len().to_string()));
+            count.add_css_class("task-count");
+            content.append(&count);
+        }
+        button.set_child(Some(&content));
+
+        if self.config.tooltip {
+            let titles: Vec<&str> = group.windows.iter().map(|w| w.title.as_str()).collect();
+            button.set_tooltip_text(Some(&titles.join("\n")));
+        }
+
+        // Left click focuses, cycling through the windows of a group
+        let widget = self.clone();
+        let group_clone = group.clone();
+        button.connect_clicked(move |_| {
+            let active = widget.active.borrow().clone();
+            let window = group_clone.next(active.as_ref());
+            dispatch(DispatchType::FocusWindow(WindowIdentifier::Address(window.address.clone())));
+        });
+
+        // Middle click closes
+        let middle_click = GestureClick::new();
+        middle_click.set_button(gdk::BUTTON_MIDDLE);
+        let widget = self.clone();
+        let group_clone = group.clone();
+        middle_click.connect_released(move |_, _, _, _| {
+            let active = widget.active.borrow().clone();
+            let window = group_clone.primary(active.as_ref());
+            dispatch(DispatchType::CloseWindow(WindowIdentifier::Address(window.address.clone())));
+        });
+        button.add_controller(middle_click);
+
+        // Right click shows window actions
+        let right_click = GestureClick::new();
+        right_click.set_button(gdk::BUTTON_SECONDARY);
+        let widget = self.clone();
+        let group_clone = group.clone();
+        right_click.connect_pressed(move |gesture, _, _, _| {
+            gesture.set_state(gtk::EventSequenceState::Claimed);
+            if let Some(button) = gesture.widget() {
+                let active = widget.active.borrow().clone();
+                let window = group_clone.primary(active.as_ref());
+                show_window_menu(&button, window.address.clone(), window.workspace_id);
+            }
+        });
+        button.add_controller(right_click);
+
+        button
+    }
+}
+
+fn send_refresh(tx: &async_channel::Sender<()>) {
+    if let Err(e) = tx.send_blocking(()) {
+        LOG.error(&format!("Failed to send taskbar event: {}", e));
+    }
+}
+
+fn dispatch(action: DispatchType) {
+    if let Err(e) = Dispatch::call(action) {
+        LOG.error(&format!("Failed to dispatch taskbar action: {}", e));
+    }
+}
+
+// Popover with fullscreen, float, close and move to workspace for a window
+fn show_window_menu(parent: &gtk::Widget, address: Address, workspace_id: i32) {
+    let menu = Menu::new();
+    menu.append(Some("Fullscreen"), Some("task.fullscreen"));
+    menu.append(Some("Toggle floating"), Some("task.float"));
+
+    // Existing workspaces and the next free one
+    let mut ids: Vec<i32> = Workspaces::get()
+        .map(|workspaces| workspaces.into_iter().map(|w| w.id).filter(|id| *id > 0).collect())
+        .unwrap_or_default();
+    ids.sort();
+    ids.push(ids.last().copied().unwrap_or(0) + 1);
+
+    let move_menu = Menu::new();
+    for id in ids.into_iter().filter(|id| *id != workspace_id) {
+        move_menu.append(Some(&format!("Workspace {}", id)), Some(&format!("task.move({})", id)));
+    }
+    menu.append_submenu(Some("Move to workspace"), &move_menu);
+    menu.append(Some("Close"), Some("task.close"));
+
+    let actions = SimpleActionGroup::new();
+
+    let fullscreen = SimpleAction::new("fullscreen", None);
+    let address_clone = address.clone();
+    fullscreen.connect_activate(move |_, _| {
+        // fullscreen only acts on the focused window
+        dispatch(DispatchType::FocusWindow(WindowIdentifier::Address(address_clone.clone())));
+        dispatch(DispatchType::ToggleFullscreen(FullscreenType::Real));
+    });
+    actions.add_action(&fullscreen);
+
+    let float = SimpleAction::new("float", None);
+    let address_clone = address.clone();
+    float.connect_activate(move |_, _| {
+        dispatch(DispatchType::ToggleFloating(Some(WindowIdentifier::Address(address_clone.clone()))));
+    });
+    actions.add_action(&float);
+
+    let move_to = SimpleAction::new("move", Some(glib::VariantTy::INT32));
+    let address_clone = address.clone();
+    move_to.connect_activate(move |_, param| {
+        if let Some(id) = param.and_then(|p| p.get::<i32>()) {
+            dispatch(DispatchType::MoveToWorkspaceSilent(
+                WorkspaceIdentifierWithSpecial::Id(id),
+                Some(WindowIdentifier::Address(address_clone.clone())),
+            ));
+        }
+    });
+    actions.add_action(&move_to);
+
+    let close = SimpleAction::new("close", None);
+    close.connect_activate(move |_, _| {
+        dispatch(DispatchType::CloseWindow(WindowIdentifier::Address(address.clone())));
+    });
+    actions.add_action(&close);
+
+    parent.insert_action_group("task", Some(&actions));
+
+    let popover = PopoverMenu::from_model(Some(&menu));
+    popover.set_parent(parent);
+    // remove the popover once closed, the button may be rebuilt meanwhile
+    popover.connect_closed(|popover| {
+        let popover = popover.clone();
+        glib::idle_add_local_once(move || popover.unparent());
+    });
+    popover.popup();
+}
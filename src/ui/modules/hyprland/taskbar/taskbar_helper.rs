@@ -0,0 +1,83 @@
+use hyprland::data::Client;
+use hyprland::shared::{Address, MonitorId};
+
+// A window shown in the taskbar
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskWindow {
+    pub address: Address,
+    pub title: String,
+    pub workspace_id: i32,
+}
+
+// One taskbar button, a single window or all windows of an app when grouping
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskGroup {
+    pub class: String,
+    pub windows: Vec<TaskWindow>,
+    pub active: bool,
+}
+
+impl TaskGroup {
+    // Window the button acts on, the focused one if it is in the group
+    pub fn primary(&self, active: Option<&Address>) -> &TaskWindow {
+        active
+            .and_then(|address| self.windows.iter().find(|w| w.address == *address))
+            .unwrap_or(&self.windows[0])
+    }
+
+    // Window after the focused one, clicking a group cycles through its windows
+    pub fn next(&self, active: Option<&Address>) -> &TaskWindow {
+        let index = active
+            .and_then(|address| self.windows.iter().position(|w| w.address == *address))
+            .map(|i| (i + 1) % self.windows.len())
+            .unwrap_or(0);
+        &self.windows[index]
+    }
+}
+
+// Group mapped clients into taskbar buttons, ordered by workspace
+// `monitor` limits the windows to one monitor
+pub fn group_tasks(
+    clients: &[Client],
+    active: Option<&Address>,
+    monitor: Option<MonitorId>,
+    group_by_app: bool,
+) -> Vec<TaskGroup> {
+    let mut clients: Vec<&Client> = clients
+        .iter()
+        .filter(|c| c.mapped && !(c.class.is_empty() && c.title.is_empty()))
+        .filter(|c| monitor.is_none_or(|id| c.monitor == id))
+        .collect();
+    // special workspaces have negative ids, keep them last
+    clients.sort_by_key(|c| (c.workspace.id < 0, c.workspace.id, c.at.0, c.at.1));
+
+    let mut groups: Vec<TaskGroup> = Vec::new();
+    for client in clients {
+        let window = TaskWindow {
+            address: client.address.clone(),
+            title: client.title.clone(),
+            workspace_id: client.workspace.id,
+        };
+        let is_active = active == Some(&client.address);
+
+        let group = if group_by_app {
+            groups.iter_mut().find(|g| g.class == client.class)
+        } else {
+            None
+        };
+
+        match group {
+            Some(group) => {
+                group.windows.push(window);
+                group.active |= is_active;
+            }
+            None => groups.push(TaskGroup {
+                class: client.class.clone(),
+                windows: vec![window],
+                active: is_active,
+            }),
+        }
+    }
+
+    groups
+}
@@ -1 +1,2 @@
-pub mod window_title;
\ No newline at end of file
+pub mod window_title;
+pub mod window_helper;
\ No newline at end of file
@@ -0,0 +1,115 @@
+use gio::prelude::*;
+use gio::{AppInfo, DesktopAppInfo, Icon};
+use hyprland::data::Client;
+use regex::Regex;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::config::config::WindowTitleConfig;
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("window_helper",LogLevel::Debug);
+}
+
+thread_local! {
+    // window class -> icon, .desktop files are only searched once per class
+    static ICON_CACHE: RefCell<HashMap<String, Option<Icon>>> = RefCell::new(HashMap::new());
+    // window class -> desktop id eg: "firefox.desktop"
+    static DESKTOP_ID_CACHE: RefCell<HashMap<String, Option<String>>> = RefCell::new(HashMap::new());
+}
+
+// Compile rewrite rules from config, invalid patterns are skipped
+pub fn compile_rewrites(config: &WindowTitleConfig) -> Vec<(Regex, String)> {
+    config
+        .rewrite
+        .iter()
+        .filter_map(|rule| match Regex::new(&rule.pattern) {
+            Ok(regex) => Some((regex, rule.replace.clone())),
+            Err(e) => {
+                LOG.error(&format!("Invalid window title rewrite '{}': {}", rule.pattern, e));
+                None
+            }
+        })
+        .collect()
+}
+
+// Format the title of a window
+// {title}, {class}, {initial_title}, {pid}, then rewrite rules in order
+pub fn format_title(format: &str, client: &Client, rewrites: &[(Regex, String)]) -> String {
+    let mut text = format
+        .replace("{title}", &client.title)
+        .replace("{class}", &client.class)
+        .replace("{initial_title}", &client.initial_title)
+        .replace("{pid}", &client.pid.to_string());
+
+    for (regex, replace) in rewrites {
+        text = regex.replace_all(&text, replace.as_str()).into_owned();
+    }
+    text.trim().to_string()
+}
+
+// App icon from the .desktop file matching a window class
+pub fn get_app_icon(class: &str) -> Option<Icon> {
+    if class.is_empty() {
+        return None;
+    }
+
+    ICON_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .entry(class.to_string())
+            .or_insert_with(|| find_desktop_app(class).and_then(|app| app.icon()))
+            .clone()
+    })
+}
+
+// Desktop id of the app a window class belongs to
+pub fn get_desktop_id(class: &str) -> Option<String> {
+    if class.is_empty() {
+        return None;
+    }
+
+    DESKTOP_ID_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .entry(class.to_string())
+            .or_insert_with(|| find_desktop_app(class).and_then(|app| app.id()).map(|id| id.to_string()))
+            .clone()
+    })
+}
+
+// Match desktop id first ("firefox.desktop", "org.gnome.Nautilus.desktop"),
+// then StartupWMClass, then the last part of reverse DNS ids
+fn find_desktop_app(class: &str) -> Option<DesktopAppInfo> {
+    let lower = class.to_lowercase();
+    for id in [class, lower.as_str()] {
+        if let Some(app) = DesktopAppInfo::new(&format!("{}.desktop", id)) {
+            return Some(app);
+        }
+    }
+
+    let apps: Vec<DesktopAppInfo> = AppInfo::all()
+        .into_iter()
+        .filter_map(|app| app.downcast::<DesktopAppInfo>().ok())
+        .collect();
+
+    if let Some(app) = apps.iter().find(|app| {
+        app.startup_wm_class()
+            .is_some_and(|wm_class| wm_class.eq_ignore_ascii_case(class))
+    }) {
+        return Some(app.clone());
+    }
+
+    apps.into_iter().find(|app| {
+        app.id()
+            .and_then(|id| {
+                id.trim_end_matches(".desktop")
+                    .rsplit('.')
+                    .next()
+                    .map(|name| name.eq_ignore_ascii_case(class))
+            })
+            .unwrap_or(false)
+    })
+}
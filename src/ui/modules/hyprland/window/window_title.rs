@@ -1,14 +1,16 @@
 // hyprland window title module
 use gtk::pango::EllipsizeMode;
 use gtk::prelude::*;
-use gtk::{Box, Label, Orientation};
+use gtk::{Box, Image, Label, Orientation};
 use hyprland::data::Client;
-use hyprland::event_listener::EventListener;
-use hyprland::shared::HyprDataActiveOptional;
-use std::cell::RefCell;
+use hyprland::shared::{Address, HyprDataActiveOptional};
+use regex::Regex;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
-use std::sync::mpsc;
-use std::thread;
+
+use crate::config::config::WindowTitleConfig;
+use crate::ui::modules::hyprland::event_helper::{attach_events, spawn_event_listener};
+use crate::ui::modules::hyprland::window::window_helper::{compile_rewrites, format_title, get_app_icon};
 
 use crate::utils::logger::{LogLevel, Logger};
 use lazy_static::lazy_static;
@@ -16,27 +18,47 @@ use lazy_static::lazy_static;
 lazy_static! {
     static ref LOG: Logger = Logger::new("window_title",LogLevel::Debug);
 }
+
+#[derive(Clone)]
 pub struct WindowWidget {
     container: Box,
-    title_label: Rc<RefCell<Label>>,
+    icon: Image,
+    title_label: Label,
+    config: Rc<WindowTitleConfig>,
+    rewrites: Rc<Vec<(Regex, String)>>,
+    active: Rc<RefCell<Option<Client>>>, // focused window, None on empty workspace
+    refresh: Rc<Cell<bool>>,             // query the active window on next update
 }
 
 impl WindowWidget {
-    pub fn new() -> Self {
+    pub fn new(config: WindowTitleConfig) -> Self {
         let container = Box::new(Orientation::Horizontal, 0);
         container.set_spacing(4);
         container.set_widget_name("window");
         container.add_css_class("modules");
 
+        // App icon
+        let icon = Image::new();
+        icon.set_pixel_size(config.icon_size);
+        icon.set_visible(false);
+        container.append(&icon);
+
         // Title label
         let title_label = Label::new(None);
-        title_label.set_ellipsize(EllipsizeMode::End);
-        title_label.set_max_width_chars(34);
+        if config.max_length > 0 {
+            title_label.set_ellipsize(EllipsizeMode::End);
+            title_label.set_max_width_chars(config.max_length as i32);
+        }
         container.append(&title_label);
 
         let widget = Self {
             container,
-            title_label: Rc::new(RefCell::new(title_label)),
+            icon,
+            title_label,
+            rewrites: Rc::new(compile_rewrites(&config)),
+            config: Rc::new(config),
+            active: Rc::new(RefCell::new(None)),
+            refresh: Rc::new(Cell::new(true)),
         };
 
         widget.update_title();
@@ -50,89 +72,119 @@ impl WindowWidget {
 
     fn update_title(&self) {
         LOG.debug("Updating title");
-        let title = match Client::get_active() {
-            Ok(Some(client)) if !client.title.trim().is_empty() => client.title,
-            _ => String::from("Desktop"),
+        if self.refresh.replace(false) {
+            *self.active.borrow_mut() = match Client::get_active() {
+                Ok(client) => client,
+                Err(e) => {
+                    LOG.error(&format!("Failed to get active window: {}", e));
+                    None
+                }
+            };
+        }
+
+        let active = self.active.borrow();
+        let (text, tooltip, icon) = match active.as_ref() {
+            Some(client) => {
+                let text = format_title(&self.config.format, client, &self.rewrites);
+                let icon = if self.config.icon { get_app_icon(&client.class) } else { None };
+                (text, client.title.clone(), icon)
+            }
+            None => (self.config.empty_format.clone(), String::new(), None),
         };
-        self.title_label.borrow().set_text(&title);
+
+        self.title_label.set_text(&text);
+        if self.config.tooltip && !tooltip.is_empty() {
+            self.title_label.set_tooltip_text(Some(&tooltip));
+        } else {
+            self.title_label.set_tooltip_text(None);
+        }
+
+        match icon {
+            Some(icon) => {
+                self.icon.set_from_gicon(&icon);
+                self.icon.set_visible(true);
+            }
+            None => {
+                self.icon.clear();
+                self.icon.set_visible(false);
+            }
+        }
     }
 
     pub fn start_event_listener(&self) {
         LOG.debug("started event listener");
-        let (tx, rx) = mpsc::channel();
-        let label = self.title_label.clone();
-
-        // Spawn the hyprland event listener in a separate thread
-        thread::spawn(move || {
-            let mut event_listener = EventListener::new();
-
-            // Helper function to get current window title
-            let get_current_title = || -> String {
-                match Client::get_active() {
-                    Ok(Some(client)) if !client.title.trim().is_empty() => client.title,
-                    _ => "Desktop".to_string(),
-                }
-            };
+        let (tx, rx) = async_channel::unbounded();
 
+        // Active window may have changed while hyprland was gone
+        spawn_event_listener("window_title", tx, WindowEvent::Refresh, |event_listener, tx| {
             let tx1 = tx.clone();
-            event_listener.add_active_window_changed_handler(move |_| {
+            event_listener.add_active_window_changed_handler(move |data| {
                 LOG.debug("active window changed");
-                let title = get_current_title();
-                if let Err(e) = tx1.send(title) {
-                    eprintln!("Failed to send window title: {}", e);
+                if let Err(e) = tx1.send_blocking(WindowEvent::Active(data.map(|d| d.address))) {
+                    LOG.error(&format!("Failed to send window title: {}", e));
                 }
             });
 
             let tx2 = tx.clone();
-            event_listener.add_workspace_changed_handler(move |_| {
-                LOG.debug("active workspace changed");
-                let title = get_current_title();
-                if let Err(e) = tx2.send(title) {
-                    eprintln!("Failed to send window title: {}", e);
+            event_listener.add_window_title_changed_handler(move |data| {
+                if let Err(e) = tx2.send_blocking(WindowEvent::Title(data.address, data.title)) {
+                    LOG.error(&format!("Failed to send window title: {}", e));
                 }
             });
 
             let tx3 = tx.clone();
-            event_listener.add_window_closed_handler(move |_| {
-                LOG.debug("active window closed");
-                let title = get_current_title();
-                if let Err(e) = tx3.send(title) {
+            event_listener.add_workspace_changed_handler(move |_| {
+                LOG.debug("active workspace changed");
+                if let Err(e) = tx3.send_blocking(WindowEvent::Refresh) {
                     LOG.error(&format!("Failed to send window title: {}", e));
                 }
             });
 
             let tx4 = tx.clone();
-            event_listener.add_window_opened_handler(move |_| {
-                LOG.debug("hyprland-window -> active window closed");
-                let title = get_current_title();
-                if let Err(e) = tx4.send(title) {
-                    eprintln!("Failed to send window title: {}", e);
+            event_listener.add_window_closed_handler(move |_| {
+                LOG.debug("active window closed");
+                if let Err(e) = tx4.send_blocking(WindowEvent::Refresh) {
+                    LOG.error(&format!("Failed to send window title: {}", e));
                 }
             });
-
-            if let Err(e) = event_listener.start_listener() {
-                LOG.error("hyprland-window -> Failed to start window title listener");
-                LOG.error(&e.to_string());
-            }
         });
 
-        let mut last_update = std::time::Instant::now();
-        let debounce_duration = std::time::Duration::from_millis(100);
-
-        glib::timeout_add_local(std::time::Duration::from_millis(100),  move || {
-            if let Ok(title) = rx.try_recv() {
-                let now = std::time::Instant::now();
-                if now.duration_since(last_update) >= debounce_duration {
-                    label.borrow().set_text(&title);
-                    last_update = now;
+        // Keep the latest state, the label is set once per frame
+        let active = self.active.clone();
+        let refresh = self.refresh.clone();
+        let widget = self.clone();
+        attach_events(
+            &self.container,
+            rx,
+            move |event| match event {
+                WindowEvent::Active(None) => {
+                    *active.borrow_mut() = None;
+                    refresh.set(false);
                 }
-            }
-
-            if !label.borrow().is_visible() {
-                glib::ControlFlow::Break
-            } else {
-                glib::ControlFlow::Continue
-            }
-        });
+                WindowEvent::Active(Some(address)) => {
+                    // class, initial title and pid are not part of the event
+                    let known = active.borrow().as_ref().is_some_and(|c| c.address == address);
+                    if !known {
+                        refresh.set(true);
+                    }
+                }
+                WindowEvent::Title(address, title) => {
+                    if let Some(client) = active.borrow_mut().as_mut()
+                        && client.address == address
+                    {
+                        client.title = title;
+                    }
+                }
+                WindowEvent::Refresh => refresh.set(true),
+            },
+            move || widget.update_title(),
+        );
     }
 }
+
+#[derive(Debug, Clone)]
+enum WindowEvent {
+    Active(Option<Address>), // focused window changed
+    Title(Address, String),  // title of any window changed
+    Refresh,                 // query the active window again
+}
@@ -1 +1,2 @@
-pub mod workspaces;
\ No newline at end of file
+pub mod workspaces;
+pub mod workspace_helper;
\ No newline at end of file
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use hyprland::data::Client;
+use hyprland::shared::Address;
+
+use crate::config::config::WorkspacesConfig;
+
+// Format workspace button text
+// labels from config replace the hyprland name,
+// {icons} are added as separate widgets so they are removed here
+pub fn format_workspace_label(
+    format: &str,
+    id: i32,
+    name: &str,
+    windows: u16,
+    config: &WorkspacesConfig,
+) -> String {
+    let name = config
+        .labels
+        .get(&id.to_string())
+        .map(String::as_str)
+        .unwrap_or(name);
+
+    let text = format
+        .replace("{id}", &id.to_string())
+        .replace("{name}", name)
+        .replace("{windows}", &windows.to_string())
+        .replace("{icons}", "");
+    text.trim().to_string()
+}
+
+// Icon of a window shown on the workspace button
+// address is used to move the window when it is dragged
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowIcon {
+    pub address: Address,
+    pub class: String,
+    pub icon: String,
+}
+
+// Icons of the windows on each workspace
+pub fn get_workspace_icons(clients: &[Client], config: &WorkspacesConfig) -> HashMap<i32, Vec<WindowIcon>> {
+    let mut icons: HashMap<i32, Vec<WindowIcon>> = HashMap::new();
+
+    for client in clients.iter().filter(|c| c.mapped) {
+        if let Some(icon) = get_window_icon(&client.class, config) {
+            icons.entry(client.workspace.id).or_default().push(WindowIcon {
+                address: client.address.clone(),
+                class: client.class.clone(),
+                icon: icon.to_string(),
+            });
+        }
+    }
+
+    icons
+}
+
+// Split format around {icons} as icons are separate widgets
+// "{id} {icons}" -> ("{id} ", "")
+pub fn split_icons_format(format: &str) -> (&str, &str) {
+    format.split_once("{icons}").unwrap_or((format, ""))
+}
+
+// Map window class to icon, class match is case insensitive
+fn get_window_icon<'a>(class: &str, config: &'a WorkspacesConfig) -> Option<&'a str> {
+    let icon = config
+        .window_icons
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(class))
+        .map(|(_, icon)| icon.as_str());
+
+    match icon {
+        Some(icon) => Some(icon),
+        None if !config.default_window_icon.is_empty() => Some(&config.default_window_icon),
+        None => None,
+    }
+}
+
+// Name used by hyprland to toggle a special workspace
+// "special:scratch" -> Some("scratch"), "special" -> None
+pub fn special_workspace_name(name: &str) -> Option<&str> {
+    name.strip_prefix("special:").filter(|n| !n.is_empty())
+}
@@ -1,15 +1,22 @@
 // Hyprand workspace module
-use gtk::{Box, Button, Label, Orientation};
-use gtk::{glib, prelude::*};
-use hyprland::data::{Workspace, Workspaces};
-use hyprland::dispatch::{Dispatch, DispatchType, WorkspaceIdentifierWithSpecial};
-use hyprland::event_listener::EventListener;
-use hyprland::shared::{HyprData, HyprDataActive, HyprDataVec};
+use gtk::{
+    Box, Button, DragSource, DropTarget, EventControllerScroll, EventControllerScrollFlags,
+    GestureClick, Label, Orientation, Revealer, RevealerTransitionType,
+};
+use gtk::{gdk, glib, prelude::*};
+use hyprland::data::{Clients, Monitors, Workspace, Workspaces};
+use hyprland::dispatch::{Dispatch, DispatchType, WindowIdentifier, WorkspaceIdentifierWithSpecial};
+use hyprland::shared::{Address, HyprData, HyprDataActive, HyprDataVec};
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
-use std::sync::mpsc;
-use std::thread;
 
+use crate::config::config::WorkspacesConfig;
+use crate::ui::modules::hyprland::event_helper::{attach_events, spawn_event_listener};
+use crate::ui::modules::hyprland::workspace::workspace_helper::{
+    format_workspace_label, get_workspace_icons, special_workspace_name, split_icons_format,
+    WindowIcon,
+};
 use crate::utils::logger::{LogLevel, Logger};
 use lazy_static::lazy_static;
 
@@ -20,23 +27,75 @@ lazy_static! {
 #[derive(Clone)]
 pub struct WorkspaceWidget {
     container: Box,
-    workspaces: Rc<RefCell<Vec<i32>>>,
-    current_workspace: Rc<RefCell<i32>>,
+    buttons_box: Box,        // regular workspace buttons
+    special_box: Box,        // toggle button and special workspaces
+    special_toggle: Button,
+    special_revealer: Revealer,
+    special_buttons_box: Box,
+    output: Option<String>, // monitor this bar is on, None shows all
+    config: WorkspacesConfig,
+    state: Rc<RefCell<WorkspaceState>>,
+    urgent_windows: Rc<RefCell<HashSet<Address>>>,
 }
 
 impl WorkspaceWidget {
-    pub fn new() -> Self {
+    pub fn new(output: Option<String>, config: WorkspacesConfig) -> Self {
         let container = Box::new(Orientation::Horizontal, 5);
         container.set_widget_name("workspaces");
 
+        let buttons_box = Box::new(Orientation::Horizontal, 5);
+        container.append(&buttons_box);
+
+        // Special workspaces group, hidden until there is a special workspace
+        let special_box = Box::new(Orientation::Horizontal, 5);
+        special_box.set_widget_name("special-workspaces");
+        special_box.set_visible(false);
+
+        let special_toggle = Button::with_label(&config.special_icon);
+        special_toggle.set_widget_name("ws-button");
+        special_toggle.add_css_class("special-toggle");
+        special_toggle.set_tooltip_text(Some("Special workspaces"));
+        special_box.append(&special_toggle);
+
+        let special_revealer = Revealer::new();
+        special_revealer.set_transition_type(RevealerTransitionType::SlideRight);
+        let special_buttons_box = Box::new(Orientation::Horizontal, 5);
+        special_revealer.set_child(Some(&special_buttons_box));
+        special_box.append(&special_revealer);
+
+        let revealer = special_revealer.clone();
+        let group = special_box.clone();
+        special_toggle.connect_clicked(move |toggle| {
+            let expanded = !revealer.reveals_child();
+            revealer.set_reveal_child(expanded);
+            if expanded {
+                group.add_css_class("expanded");
+                toggle.remove_css_class("urgent");
+            } else {
+                group.remove_css_class("expanded");
+            }
+        });
+
+        if config.show_special {
+            container.append(&special_box);
+        }
+
         let widget = Self {
             container,
-            workspaces: Rc::new(RefCell::new(Vec::new())),
-            current_workspace: Rc::new(RefCell::new(1)),
+            buttons_box,
+            special_box,
+            special_toggle,
+            special_revealer,
+            special_buttons_box,
+            output,
+            config,
+            state: Rc::new(RefCell::new(WorkspaceState::default())),
+            urgent_windows: Rc::new(RefCell::new(HashSet::new())),
         };
 
         widget.update_workspaces();
         widget.start_event_listener();
+        widget.setup_scroll();
 
         widget
     }
@@ -45,65 +104,187 @@ impl WorkspaceWidget {
         &self.container
     }
 
-    fn start_event_listener(&self) {
-        let (tx, rx) = mpsc::channel();
-        let widget_clone = self.clone();
+    // Scroll to cycle through workspaces of this bar
+    fn setup_scroll(&self) {
+        let scroll = EventControllerScroll::new(
+            EventControllerScrollFlags::VERTICAL | EventControllerScrollFlags::DISCRETE,
+        );
+        let widget = self.clone();
+        scroll.connect_scroll(move |_, _dx, dy| {
+            if dy > 0.0 {
+                widget.scroll_workspace(1);
+            } else if dy < 0.0 {
+                widget.scroll_workspace(-1);
+            }
+            glib::Propagation::Stop
+        });
+        self.buttons_box.add_controller(scroll);
+    }
+
+    fn scroll_workspace(&self, direction: i32) {
+        let state = self.state.borrow();
 
+        // Focused workspace may be on another monitor, use the one shown here
+        let current_id = if state.workspaces.iter().any(|w| w.id == state.current_id) {
+            state.current_id
+        } else {
+            match state.workspaces.iter().find(|w| state.visible_ids.contains(&w.id)) {
+                Some(workspace) => workspace.id,
+                None => return,
+            }
+        };
+
+        let ids: Vec<i32> = state
+            .workspaces
+            .iter()
+            .filter(|w| !self.config.scroll_skip_empty || w.windows > 0 || w.id == current_id)
+            .map(|w| w.id)
+            .collect();
+
+        let Some(index) = ids.iter().position(|&id| id == current_id) else {
+            return;
+        };
+
+        let len = ids.len() as i32;
+        let next = index as i32 + direction;
+        let next = if self.config.scroll_wrap {
+            next.rem_euclid(len)
+        } else {
+            next.clamp(0, len - 1)
+        };
 
-        thread::spawn(move || {
-            let mut event_listener = EventListener::new();
+        let target_id = ids[next as usize];
+        if target_id != current_id {
+            Self::switch_to_workspace(target_id);
+        }
+    }
 
+    fn start_event_listener(&self) {
+        let (tx, rx) = async_channel::unbounded();
+
+        // Workspaces may have changed while hyprland was gone
+        spawn_event_listener("workspaces", tx, WorkspaceEvent::Changed, |event_listener, tx| {
             let tx_clone = tx.clone();
             event_listener.add_workspace_changed_handler(move |_| {
-                if let Err(e) = tx_clone.send(WorkspaceEvent::Changed) {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::Changed) {
                     LOG.error(&format!("Failed to send workspace change event: {}", e));
                 }
             });
-            
+
             let tx_clone = tx.clone();
             event_listener.add_workspace_added_handler(move |_| {
-                if let Err(e) = tx_clone.send(WorkspaceEvent::Added) {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::Added) {
                     LOG.error(&format!("Failed to send workspace added event: {}", e));
                 }
             });
 
             let tx_clone = tx.clone();
             event_listener.add_workspace_deleted_handler(move |_| {
-                if let Err(e) = tx_clone.send(WorkspaceEvent::Destroyed) {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::Destroyed) {
                     LOG.error(&format!("Failed to send workspace destroyed event: {}", e));
                 }
             });
 
-            // Start listening
-            if let Err(e) = event_listener.start_listener() {
-                LOG.error(&format!("Failed to start event listener: {}", e));
-            }
+            let tx_clone = tx.clone();
+            event_listener.add_workspace_renamed_handler(move |_| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::Renamed) {
+                    LOG.error(&format!("Failed to send workspace renamed event: {}", e));
+                }
+            });
+
+            // Special workspace opened or closed on a monitor
+            let tx_clone = tx.clone();
+            event_listener.add_changed_special_handler(move |_| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::SpecialChanged) {
+                    LOG.error(&format!("Failed to send special workspace event: {}", e));
+                }
+            });
+
+            let tx_clone = tx.clone();
+            event_listener.add_special_removed_handler(move |_| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::SpecialChanged) {
+                    LOG.error(&format!("Failed to send special workspace event: {}", e));
+                }
+            });
+
+            let tx_clone = tx.clone();
+            event_listener.add_urgent_state_changed_handler(move |address| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::Urgent(address)) {
+                    LOG.error(&format!("Failed to send urgent event: {}", e));
+                }
+            });
+
+            // Window events change window count and icons
+            let tx_clone = tx.clone();
+            event_listener.add_window_opened_handler(move |_| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::WindowsChanged) {
+                    LOG.error(&format!("Failed to send window opened event: {}", e));
+                }
+            });
+
+            let tx_clone = tx.clone();
+            event_listener.add_window_closed_handler(move |_| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::WindowsChanged) {
+                    LOG.error(&format!("Failed to send window closed event: {}", e));
+                }
+            });
+
+            let tx_clone = tx.clone();
+            event_listener.add_window_moved_handler(move |_| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::WindowsChanged) {
+                    LOG.error(&format!("Failed to send window moved event: {}", e));
+                }
+            });
+
+            let tx_clone = tx.clone();
+            event_listener.add_workspace_moved_handler(move |_| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::Moved) {
+                    LOG.error(&format!("Failed to send workspace moved event: {}", e));
+                }
+            });
+
+            // Focused workspace changes when focusing another monitor
+            let tx_clone = tx.clone();
+            event_listener.add_active_monitor_changed_handler(move |_| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::MonitorFocused) {
+                    LOG.error(&format!("Failed to send monitor focused event: {}", e));
+                }
+            });
+
+            let tx_clone = tx.clone();
+            event_listener.add_monitor_added_handler(move |_| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::MonitorAdded) {
+                    LOG.error(&format!("Failed to send monitor added event: {}", e));
+                }
+            });
+
+            let tx_clone = tx.clone();
+            event_listener.add_monitor_removed_handler(move |_| {
+                if let Err(e) = tx_clone.send_blocking(WorkspaceEvent::MonitorRemoved) {
+                    LOG.error(&format!("Failed to send monitor removed event: {}", e));
+                }
+            });
+
         });
 
-        glib::spawn_future_local(async move {
-            loop {
-                match rx.try_recv() {
-                    Ok(_) => {
-                        widget_clone.update_workspaces();
-                        LOG.debug("workspaces updated workspace");
-                    }
-                    Err(mpsc::TryRecvError::Empty) => {
-                        LOG.debug("mpcs: Workspade event -> Empty");
-                        // No events, wait a bit
-                        glib::timeout_future(std::time::Duration::from_millis(200)).await;
-                    }
-                    Err(mpsc::TryRecvError::Disconnected) => {
-                        LOG.error("mpcs: Workspade event ->  disconnected");
-                        eprintln!("Event listener disconnected");
-                        break;
+        let widget = self.clone();
+        attach_events(
+            &self.container,
+            rx,
+            {
+                let urgent_windows = self.urgent_windows.clone();
+                move |event| {
+                    if let WorkspaceEvent::Urgent(address) = event {
+                        urgent_windows.borrow_mut().insert(address);
                     }
                 }
-            }
-        });
+            },
+            move || widget.update_workspaces(),
+        );
     }
 
     pub fn update_workspaces(&self) {
-        let (new_workspace_ids, new_current_id) = match self.get_workspace_info() {
+        let new_state = match self.get_workspace_info() {
             Ok(info) => info,
             Err(e) => {
                 self.show_error(&format!("Workspace error: {:?}", e));
@@ -113,56 +294,48 @@ impl WorkspaceWidget {
             }
         };
 
-        // Check if anything actually changed
-        let workspaces_changed = {
-            let current_workspaces = self.workspaces.borrow();
-            *current_workspaces != new_workspace_ids
-        };
-
-        let current_workspace_changed = {
-            let current_ws = self.current_workspace.borrow();
-            *current_ws != new_current_id
-        };
-
-        // If only current workspace changed, just update button styles (fastest)
-        if !workspaces_changed && current_workspace_changed {
-            self.update_button_styles(&new_workspace_ids, new_current_id);
-            *self.current_workspace.borrow_mut() = new_current_id;
-            return;
-        }
-
         // If nothing changed, do nothing
-        if !workspaces_changed && !current_workspace_changed {
+        if *self.state.borrow() == new_state {
             return;
         }
 
-        // Full rebuild needed
-        *self.workspaces.borrow_mut() = new_workspace_ids.clone();
-        *self.current_workspace.borrow_mut() = new_current_id;
+        let old_state = self.state.replace(new_state.clone());
 
-        // Clear and rebuild
-        while let Some(child) = self.container.first_child() {
-            self.container.remove(&child);
+        // Check if buttons have to be created again
+        if !same_ids(&old_state.workspaces, &new_state.workspaces) {
+            self.create_workspace_buttons(&self.buttons_box, &new_state.workspaces, &new_state);
+        } else {
+            // Only labels or classes changed, just update button styles (fastest)
+            self.update_button_styles(&self.buttons_box, &old_state.workspaces, &new_state.workspaces, &new_state);
         }
 
-        self.create_workspace_buttons(&new_workspace_ids, new_current_id);
+        if !same_ids(&old_state.special, &new_state.special) {
+            self.create_workspace_buttons(&self.special_buttons_box, &new_state.special, &new_state);
+        } else {
+            self.update_button_styles(&self.special_buttons_box, &old_state.special, &new_state.special, &new_state);
+        }
+        self.update_special_group(&new_state);
     }
 
-    // only update css classes 
-    fn update_button_styles(&self, workspace_ids: &[i32], current_id: i32) {
-        let mut child = self.container.first_child();
+    // only update labels and css classes
+    fn update_button_styles(
+        &self,
+        buttons_box: &Box,
+        old_workspaces: &[WorkspaceInfo],
+        workspaces: &[WorkspaceInfo],
+        state: &WorkspaceState,
+    ) {
+        let mut child = buttons_box.first_child();
         let mut index = 0;
 
         while let Some(widget) = child {
             if let Some(button) = widget.downcast_ref::<Button>() {
-                if index < workspace_ids.len() {
-                    let workspace_id = workspace_ids[index];
-                    // Remove old classes and add new ones
-                    button.remove_css_class("active");
-
-                    if workspace_id == current_id {
-                        button.add_css_class("active");
+                if let Some(workspace) = workspaces.get(index) {
+                    let old = old_workspaces.get(index);
+                    if old.is_none_or(|old| !old.same_content(workspace)) {
+                        set_button_content(button, workspace);
                     }
+                    set_button_classes(button, workspace, state);
                 }
                 index += 1;
             }
@@ -171,46 +344,179 @@ impl WorkspaceWidget {
     }
 
     // Create workspace buttons according to workspaces
-    fn create_workspace_buttons(&self, workspace_ids: &[i32], current_id: i32) {
-        for &workspace_id in workspace_ids {
-            let button = Button::with_label(&workspace_id.to_string());
+    fn create_workspace_buttons(&self, buttons_box: &Box, workspaces: &[WorkspaceInfo], state: &WorkspaceState) {
+        // Clear and rebuild
+        while let Some(child) = buttons_box.first_child() {
+            buttons_box.remove(&child);
+        }
+
+        for workspace in workspaces {
+            let button = Button::new();
             button.set_size_request(30, 24);
             button.set_widget_name("ws-button");
             // button.add_css_class("flat");
 
-            // it sucks without flat 
-            if workspace_id == current_id {
-                button.add_css_class("active");
-            }
-            button.connect_clicked(move |_| {
-                Self::switch_to_workspace(workspace_id);
+            // it sucks without flat
+            set_button_content(&button, workspace);
+            set_button_classes(&button, workspace, state);
+
+            let workspace_id = workspace.id;
+            let special_name = workspace.special().then(|| workspace.name.clone());
+            button.connect_clicked(move |_| match special_name {
+                Some(ref name) => Self::toggle_special_workspace(name),
+                None => Self::switch_to_workspace(workspace_id),
+            });
+
+            // Middle click moves the focused window here
+            let middle_click = GestureClick::new();
+            middle_click.set_button(gdk::BUTTON_MIDDLE);
+            let workspace_name = workspace.name.clone();
+            middle_click.connect_pressed(move |gesture, _, _, _| {
+                gesture.set_state(gtk::EventSequenceState::Claimed);
+                Self::move_window_to_workspace(workspace_id, &workspace_name, None);
+            });
+            button.add_controller(middle_click);
+
+            // Window icons and other workspace buttons can be dropped here
+            let drop_target = DropTarget::new(String::static_type(), gdk::DragAction::MOVE);
+            let workspace_name = workspace.name.clone();
+            drop_target.connect_drop(move |_, value, _, _| match value.get::<String>() {
+                Ok(address) => {
+                    Self::move_window_to_workspace(workspace_id, &workspace_name, Some(Address::new(address)));
+                    true
+                }
+                Err(_) => false,
+            });
+            button.add_controller(drop_target);
+
+            // Dragging the button moves the workspace's last focused window, so it works without {icons}
+            let drag_source = DragSource::new();
+            drag_source.set_actions(gdk::DragAction::MOVE);
+            drag_source.connect_prepare(move |_, _, _| {
+                let workspace = Workspaces::get().ok()?.into_iter().find(|w| w.id == workspace_id)?;
+                if workspace.windows == 0 {
+                    return None;
+                }
+                Some(gdk::ContentProvider::for_value(&workspace.last_window.to_string().to_value()))
             });
+            button.add_controller(drag_source);
 
-            self.container.append(&button);
+            buttons_box.append(&button);
+        }
+    }
+
+    // Show the group only when there are special workspaces
+    fn update_special_group(&self, state: &WorkspaceState) {
+        self.special_box.set_visible(!state.special.is_empty());
+
+        // Let the user know about urgent windows in a collapsed group
+        if state.special.iter().any(|w| w.urgent) && !self.special_revealer.reveals_child() {
+            self.special_toggle.add_css_class("urgent");
+        } else {
+            self.special_toggle.remove_css_class("urgent");
         }
     }
 
     fn show_error(&self, error_msg: &str) {
-        while let Some(child) = self.container.first_child() {
-            self.container.remove(&child);
+        while let Some(child) = self.buttons_box.first_child() {
+            self.buttons_box.remove(&child);
         }
 
         let error_label = Label::new(Some("WS Error"));
         error_label.set_tooltip_text(Some(error_msg));
-        self.container.append(&error_label);
+        self.buttons_box.append(&error_label);
+
+        // Buttons are created again on next update
+        *self.state.borrow_mut() = WorkspaceState::default();
     }
 
-    fn get_workspace_info(&self) -> Result<(Vec<i32>, i32), hyprland::shared::HyprError> {
+    /// Returns workspaces and special workspaces for this bar,
+    /// the focused workspace id and the ids of workspaces visible on the monitors
+    fn get_workspace_info(&self) -> Result<WorkspaceState, hyprland::shared::HyprError> {
         let workspaces = Workspaces::get()?.to_vec();
-        let mut workspace_ids: Vec<i32> = workspaces.iter().map(|w| w.id).collect();
-        workspace_ids.sort();
+        let monitors = Monitors::get()?.to_vec();
+
+        // Only filter if hyprland knows the output, else show everything
+        let output = match self.output {
+            Some(ref name) if !self.config.all_outputs && monitors.iter().any(|m| &m.name == name) => {
+                Some(name.as_str())
+            }
+            _ => None,
+        };
 
         let current_id = match Workspace::get_active() {
             Ok(workspace) => workspace.id,
             Err(_) => 1, // Default fallback
         };
 
-        Ok((workspace_ids, current_id))
+        // Only ask for clients if icons or urgent windows are shown
+        let needs_clients = self.config.format.contains("{icons}")
+            || self.config.special_format.contains("{icons}")
+            || !self.urgent_windows.borrow().is_empty();
+        let clients = if needs_clients {
+            Clients::get()?.to_vec()
+        } else {
+            Vec::new()
+        };
+        let icons = get_workspace_icons(&clients, &self.config);
+
+        // Urgent until the workspace is visited or the window is closed,
+        // special workspaces are never active so being shown counts as visited
+        let shown_ids: Vec<i32> = monitors
+            .iter()
+            .flat_map(|m| [m.active_workspace.id, m.special_workspace.id])
+            .collect();
+        let mut urgent_ids = HashSet::new();
+        self.urgent_windows.borrow_mut().retain(|address| {
+            match clients.iter().find(|c| &c.address == address) {
+                Some(client) if client.workspace.id != current_id && !shown_ids.contains(&client.workspace.id) => {
+                    urgent_ids.insert(client.workspace.id);
+                    true
+                }
+                _ => false,
+            }
+        });
+
+        // Special workspaces have negative ids
+        let mut workspace_infos: Vec<WorkspaceInfo> = workspaces
+            .iter()
+            .filter(|w| w.id >= 0)
+            .filter(|w| output.is_none_or(|name| w.monitor == name))
+            .map(|w| WorkspaceInfo::new(w.id, &w.name, w.windows, &icons, &urgent_ids, &self.config))
+            .collect();
+
+        // Persistent workspaces which doesn't exist yet are shown on every bar
+        for &id in &self.config.persistent {
+            if !workspaces.iter().any(|w| w.id == id) {
+                workspace_infos.push(WorkspaceInfo::new(id, &id.to_string(), 0, &icons, &urgent_ids, &self.config));
+            }
+        }
+        workspace_infos.sort_by_key(|w| w.id);
+
+        // Special workspaces can be opened on any monitor so show all of them
+        let mut special_infos: Vec<WorkspaceInfo> = Vec::new();
+        if self.config.show_special {
+            special_infos = workspaces
+                .iter()
+                .filter(|w| w.id < 0)
+                .map(|w| WorkspaceInfo::new(w.id, &w.name, w.windows, &icons, &urgent_ids, &self.config))
+                .collect();
+            special_infos.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        let visible_ids: Vec<i32> = monitors
+            .iter()
+            .filter(|m| output.is_none_or(|name| m.name == name))
+            .flat_map(|m| [m.active_workspace.id, m.special_workspace.id])
+            .filter(|&id| id != 0)
+            .collect();
+
+        Ok(WorkspaceState {
+            workspaces: workspace_infos,
+            special: special_infos,
+            current_id,
+            visible_ids,
+        })
     }
 
     fn switch_to_workspace(workspace_id: i32) {
@@ -222,14 +528,173 @@ impl WorkspaceWidget {
         }
     }
 
+    // Move a window (focused if None) without switching to the workspace
+    fn move_window_to_workspace(workspace_id: i32, workspace_name: &str, window: Option<Address>) {
+        let workspace = if workspace_id < 0 {
+            WorkspaceIdentifierWithSpecial::Special(special_workspace_name(workspace_name))
+        } else {
+            WorkspaceIdentifierWithSpecial::Id(workspace_id)
+        };
+
+        if let Err(e) = Dispatch::call(DispatchType::MoveToWorkspaceSilent(
+            workspace,
+            window.map(WindowIdentifier::Address),
+        )) {
+            eprintln!("Failed to move window to workspace {}: {}", workspace_name, e);
+        }
+    }
+
+    // Special workspaces are toggled on the focused monitor
+    fn toggle_special_workspace(name: &str) {
+        let special_name = special_workspace_name(name).map(str::to_string);
+        if let Err(e) = Dispatch::call(DispatchType::ToggleSpecialWorkspace(special_name)) {
+            eprintln!("Failed to toggle special workspace {}: {}", name, e);
+        }
+    }
+
     pub fn refresh(&self) {
         self.update_workspaces();
     }
 }
 
+fn same_ids(old: &[WorkspaceInfo], new: &[WorkspaceInfo]) -> bool {
+    old.len() == new.len() && old.iter().zip(new).all(|(a, b)| a.id == b.id)
+}
+
+// Button child is the label text with the window icons in between
+fn set_button_content(button: &Button, workspace: &WorkspaceInfo) {
+    let content = Box::new(Orientation::Horizontal, 4);
+    content.set_halign(gtk::Align::Center);
+
+    if !workspace.label.is_empty() {
+        content.append(&Label::new(Some(&workspace.label)));
+    }
+
+    for window_icon in &workspace.window_icons {
+        let icon = Label::new(Some(&window_icon.icon));
+        icon.add_css_class("window-icon");
+        icon.set_tooltip_text(Some(&window_icon.class));
+
+        // Drag the icon to another workspace to move the window
+        let drag_source = DragSource::new();
+        drag_source.set_actions(gdk::DragAction::MOVE);
+        let address = window_icon.address.to_string();
+        drag_source.connect_prepare(move |_, _, _| {
+            Some(gdk::ContentProvider::for_value(&address.to_value()))
+        });
+        icon.add_controller(drag_source);
+
+        content.append(&icon);
+    }
+
+    if !workspace.label_end.is_empty() {
+        content.append(&Label::new(Some(&workspace.label_end)));
+    }
+
+    button.set_child(Some(&content));
+}
+
+fn set_button_classes(button: &Button, workspace: &WorkspaceInfo, state: &WorkspaceState) {
+    // Remove old classes and add new ones
+    for class in ["active", "visible", "empty", "urgent", "special"] {
+        button.remove_css_class(class);
+    }
+
+    if workspace.id == state.current_id {
+        button.add_css_class("active");
+    }
+    // shown on this monitor but maybe not focused
+    if state.visible_ids.contains(&workspace.id) {
+        button.add_css_class("visible");
+    }
+    // persistent workspace with no windows
+    if workspace.windows == 0 {
+        button.add_css_class("empty");
+    }
+    if workspace.urgent {
+        button.add_css_class("urgent");
+    }
+    if workspace.special() {
+        button.add_css_class("special");
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct WorkspaceState {
+    workspaces: Vec<WorkspaceInfo>,
+    special: Vec<WorkspaceInfo>,
+    current_id: i32,
+    visible_ids: Vec<i32>, // active and special workspaces shown on the monitors
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct WorkspaceInfo {
+    id: i32,
+    name: String,      // hyprland name, eg: "special:scratch"
+    label: String,     // formatted button text before {icons}
+    label_end: String, // formatted button text after {icons}
+    window_icons: Vec<WindowIcon>,
+    windows: u16,
+    urgent: bool,
+}
+
+impl WorkspaceInfo {
+    fn new(
+        id: i32,
+        name: &str,
+        windows: u16,
+        icons: &HashMap<i32, Vec<WindowIcon>>,
+        urgent_ids: &HashSet<i32>,
+        config: &WorkspacesConfig,
+    ) -> Self {
+        let (format, display_name) = if id < 0 {
+            (&config.special_format, special_workspace_name(name).unwrap_or(name))
+        } else {
+            (&config.format, name)
+        };
+
+        let (format_start, format_end) = split_icons_format(format);
+        let window_icons = if format.contains("{icons}") {
+            icons.get(&id).cloned().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            id,
+            name: name.to_string(),
+            label: format_workspace_label(format_start, id, display_name, windows, config),
+            label_end: format_workspace_label(format_end, id, display_name, windows, config),
+            window_icons,
+            windows,
+            urgent: urgent_ids.contains(&id),
+        }
+    }
+
+    // Button child has to be created again if this is false
+    fn same_content(&self, other: &WorkspaceInfo) -> bool {
+        self.label == other.label
+            && self.label_end == other.label_end
+            && self.window_icons == other.window_icons
+    }
+
+    // Special workspaces have negative ids
+    fn special(&self) -> bool {
+        self.id < 0
+    }
+}
+
 #[derive(Debug, Clone)]
 enum WorkspaceEvent {
     Changed,
     Added,
     Destroyed,
+    Renamed,
+    Moved,
+    SpecialChanged,
+    Urgent(Address),
+    WindowsChanged,
+    MonitorFocused,
+    MonitorAdded,
+    MonitorRemoved,
 }
@@ -0,0 +1,221 @@
+// Idle inhibitor module, one state shared by the bars of every monitor
+use gtk::{glib, prelude::*, Button};
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use crate::config::config::IdleInhibitorConfig;
+use crate::system::ipc;
+use crate::ui::modules::idle_inhibitor::idle_inhibitor_helper::{
+    format_remaining, logind_inhibit, Inhibitor, WaylandIdle,
+};
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("idle_inhibitor",LogLevel::Debug);
+}
+
+thread_local! {
+    static SERVICE: RefCell<Option<Rc<IdleInhibitorService>>> = const { RefCell::new(None) };
+}
+
+fn now() -> i64 {
+    glib::real_time() / 1_000_000
+}
+
+struct IdleInhibitorService {
+    config: IdleInhibitorConfig,
+    wayland: Option<WaylandIdle>,  // bound once, None on logind only setups
+    inhibitor: RefCell<Option<Inhibitor>>,
+    requested: Cell<bool>,         // on, even while the logind lock is still being taken
+    until: Cell<Option<i64>>,      // unix seconds of the auto timeout
+    generation: Cell<u32>,         // bumped on each change so stale timeouts do nothing
+    buttons: RefCell<Vec<Button>>, // the first one's surface carries the wayland inhibitor
+}
+
+impl IdleInhibitorService {
+    fn get(config: &IdleInhibitorConfig) -> Rc<Self> {
+        SERVICE.with(|service| {
+            service
+                .borrow_mut()
+                .get_or_insert_with(|| {
+                    let wayland = match config.backend.as_str() {
+                        "logind" => None,
+                        _ => WaylandIdle::new(),
+                    };
+                    let service = Rc::new(Self {
+                        config: config.clone(),
+                        wayland,
+                        inhibitor: RefCell::new(None),
+                        requested: Cell::new(false),
+                        until: Cell::new(None),
+                        generation: Cell::new(0),
+                        buttons: RefCell::new(Vec::new()),
+                    });
+                    service.register_ipc();
+                    service
+                })
+                .clone()
+        })
+    }
+
+    // minutes: None uses the configured timeout, Some(0) keeps it on
+    fn set_active(self: &Rc<Self>, active: bool, minutes: Option<u32>) {
+        let generation = self.generation.get() + 1;
+        self.generation.set(generation);
+        self.requested.set(active);
+        // dropping the inhibitor releases it
+        self.inhibitor.replace(None);
+        self.until.set(None);
+
+        if active {
+            let minutes = minutes.unwrap_or(self.config.timeout);
+            if minutes > 0 {
+                self.until.set(Some(now() + minutes as i64 * 60));
+                let weak: Weak<Self> = Rc::downgrade(self);
+                glib::timeout_add_local_once(Duration::from_secs(minutes as u64 * 60), move || {
+                    if let Some(service) = weak.upgrade()
+                        && service.generation.get() == generation
+                    {
+                        LOG.debug("Timeout reached, turning off");
+                        service.set_active(false, None);
+                    }
+                });
+            }
+            self.acquire(generation);
+        }
+        self.update();
+    }
+
+    fn acquire(self: &Rc<Self>, generation: u32) {
+        let backend = self.config.backend.as_str();
+        if backend != "logind" {
+            let anchor = self.buttons.borrow().first().cloned();
+            let inhibitor = self.wayland.as_ref().zip(anchor).and_then(|(wayland, button)| wayland.inhibit(&button));
+            if let Some(inhibitor) = inhibitor {
+                LOG.debug("Inhibiting idle on the bar surface");
+                self.inhibitor.replace(Some(inhibitor));
+                return;
+            }
+            if backend == "wayland" {
+                LOG.error("Compositor has no zwp_idle_inhibit_manager_v1");
+                self.requested.set(false);
+                return;
+            }
+        }
+
+        let weak = Rc::downgrade(self);
+        glib::spawn_future_local(async move {
+            let result = logind_inhibit().await;
+            let Some(service) = weak.upgrade() else {
+                return;
+            };
+            // turned off or changed again while waiting
+            if service.generation.get() != generation {
+                return;
+            }
+            match result {
+                Ok(inhibitor) => {
+                    LOG.debug("Holding a logind idle inhibitor lock");
+                    service.inhibitor.replace(Some(inhibitor));
+                }
+                Err(e) => {
+                    LOG.error(&format!("Failed to take a logind inhibitor lock: {}", e));
+                    service.requested.set(false);
+                }
+            }
+            service.update();
+        });
+    }
+
+    fn update(&self) {
+        let active = self.requested.get();
+        let icon = if active { &self.config.activated_icon } else { &self.config.deactivated_icon };
+
+        let tooltip = match (active, self.until.get()) {
+            (false, _) => "Idle inhibitor off".to_string(),
+            (true, None) => "Idle inhibitor on".to_string(),
+            (true, Some(until)) => {
+                let time = glib::DateTime::from_unix_local(until)
+                    .and_then(|time| time.format("%H:%M"))
+                    .map(|time| time.to_string())
+                    .unwrap_or_default();
+                format!("Idle inhibitor on until {}", time)
+            }
+        };
+
+        for button in self.buttons.borrow().iter() {
+            button.set_label(icon);
+            if active {
+                button.remove_css_class("deactivated");
+                button.add_css_class("activated");
+            } else {
+                button.remove_css_class("activated");
+                button.add_css_class("deactivated");
+            }
+            if self.config.tooltip {
+                button.set_tooltip_text(Some(&tooltip));
+            }
+        }
+    }
+
+    fn status(&self) -> String {
+        let backend = self.inhibitor.borrow().as_ref().map(Inhibitor::backend).unwrap_or("pending");
+        match (self.requested.get(), self.until.get()) {
+            (false, _) => "off".to_string(),
+            (true, None) => format!("on\t{}", backend),
+            (true, Some(until)) => format!("on\t{}\t{} left", backend, format_remaining(until - now())),
+        }
+    }
+
+    fn register_ipc(self: &Rc<Self>) {
+        let weak = Rc::downgrade(self);
+        ipc::register("idle_inhibitor", move |args| {
+            let Some(service) = weak.upgrade() else {
+                return Err("idle inhibitor is not available".to_string());
+            };
+            // only on and toggle take minutes
+            let minutes = || match args.get(1) {
+                Some(text) => text.parse::<u32>().map(Some).map_err(|_| format!("invalid minutes '{}'", text)),
+                None => Ok(None),
+            };
+            match args.first().map(String::as_str).unwrap_or("status") {
+                "on" => service.set_active(true, minutes()?),
+                "off" => service.set_active(false, None),
+                "toggle" => service.set_active(!service.requested.get(), minutes()?),
+                "status" => {}
+                command => {
+                    return Err(format!("unknown idle_inhibitor command '{}', use on [minutes], off, toggle or status", command));
+                }
+            }
+            Ok(service.status())
+        });
+    }
+}
+
+pub struct IdleInhibitor {
+    button: Button,
+}
+
+impl IdleInhibitor {
+    pub fn new(config: IdleInhibitorConfig) -> Self {
+        let button = Button::new();
+        button.set_widget_name("idle-inhibitor");
+        button.add_css_class("modules");
+
+        let service = IdleInhibitorService::get(&config);
+        service.buttons.borrow_mut().push(button.clone());
+        service.update();
+
+        button.connect_clicked(move |_| {
+            service.set_active(!service.requested.get(), None);
+        });
+
+        Self { button }
+    }
+
+    pub fn widget(&self) -> &Button {
+        &self.button
+    }
+}
@@ -0,0 +1,104 @@
+// Idle inhibit backends: zwp_idle_inhibit_manager_v1 on a bar surface, or a logind "idle" lock
+// The wayland objects live on their own event queue so gtk's queue is never dispatched from here
+use gdk4_wayland::prelude::*;
+use gdk4_wayland::{WaylandDisplay, WaylandSurface};
+use gtk::{gdk, prelude::*};
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::wl_registry::{self, WlRegistry};
+use wayland_client::{delegate_noop, Connection as WaylandConnection, Dispatch, EventQueue, Proxy, QueueHandle};
+use wayland_protocols::wp::idle_inhibit::zv1::client::zwp_idle_inhibit_manager_v1::ZwpIdleInhibitManagerV1;
+use wayland_protocols::wp::idle_inhibit::zv1::client::zwp_idle_inhibitor_v1::ZwpIdleInhibitorV1;
+use zbus::zvariant::OwnedFd;
+use zbus::Connection;
+
+// Neither interface sends events, the registry ones are only read once by registry_queue_init
+struct IdleState;
+
+impl Dispatch<WlRegistry, GlobalListContents> for IdleState {
+    fn event(
+        _: &mut Self,
+        _: &WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &WaylandConnection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+delegate_noop!(IdleState: ignore ZwpIdleInhibitManagerV1);
+delegate_noop!(IdleState: ignore ZwpIdleInhibitorV1);
+
+// zwp_idle_inhibit_manager_v1 bound once on gtk's wayland connection
+pub struct WaylandIdle {
+    connection: WaylandConnection,
+    queue: EventQueue<IdleState>,
+    manager: ZwpIdleInhibitManagerV1,
+}
+
+impl WaylandIdle {
+    // None when not on wayland or the compositor has no idle inhibit manager
+    pub fn new() -> Option<Self> {
+        let display = gdk::Display::default()?.downcast::<WaylandDisplay>().ok()?;
+        let connection = WaylandConnection::from_backend(display.wl_display()?.backend().upgrade()?);
+        let (globals, queue) = registry_queue_init::<IdleState>(&connection).ok()?;
+        let manager = globals.bind(&queue.handle(), 1..=1, ()).ok()?;
+        Some(Self { connection, queue, manager })
+    }
+
+    // Inhibitor on the surface of the window `widget` is in
+    pub fn inhibit(&self, widget: &impl IsA<gtk::Widget>) -> Option<Inhibitor> {
+        let surface = widget.native()?.surface()?.downcast::<WaylandSurface>().ok()?;
+        let inhibitor = self.manager.create_inhibitor(&surface.wl_surface()?, &self.queue.handle(), ());
+        let _ = self.connection.flush();
+        Some(Inhibitor::Wayland { inhibitor, connection: self.connection.clone() })
+    }
+}
+
+pub enum Inhibitor {
+    Wayland { inhibitor: ZwpIdleInhibitorV1, connection: WaylandConnection },
+    Logind { _lock: OwnedFd }, // the lock is held while the fd is open
+}
+
+impl Inhibitor {
+    pub fn backend(&self) -> &'static str {
+        match self {
+            Inhibitor::Wayland { .. } => "wayland",
+            Inhibitor::Logind { .. } => "logind",
+        }
+    }
+}
+
+impl Drop for Inhibitor {
+    fn drop(&mut self) {
+        if let Inhibitor::Wayland { inhibitor, connection } = self {
+            inhibitor.destroy();
+            let _ = connection.flush();
+        }
+    }
+}
+
+// logind Inhibit("idle", ...) lock, released when the returned fd is closed
+pub async fn logind_inhibit() -> zbus::Result<Inhibitor> {
+    let connection = Connection::system().await?;
+    let reply = connection
+        .call_method(
+            Some("org.freedesktop.login1"),
+            "/org/freedesktop/login1",
+            Some("org.freedesktop.login1.Manager"),
+            "Inhibit",
+            &("idle", "better-bar", "Idle inhibitor enabled in the bar", "block"),
+        )
+        .await?;
+    let fd: OwnedFd = reply.body().deserialize()?;
+    Ok(Inhibitor::Logind { _lock: fd })
+}
+
+// "12:30" left of an auto timeout
+pub fn format_remaining(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    match seconds / 3600 {
+        0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60),
+    }
+}
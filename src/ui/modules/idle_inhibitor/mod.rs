@@ -0,0 +1,2 @@
+pub mod idle_inhibitor;
+pub mod idle_inhibitor_helper;
\ No newline at end of file
@@ -1,27 +1,62 @@
 use gtk::{
-    Button,
-    prelude::*,
+    Button, GestureClick,
+    gdk, prelude::*,
 };
-use tokio::process::Command;
+use std::rc::Rc;
+
+use crate::config::config::LauncherConfig;
+use crate::ui::modules::launcher::app_menu::create_app_menu;
+use crate::ui::modules::launcher::launcher_helper::{create_icon, describe_failure, run_command};
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("launcher",LogLevel::Debug);
+}
 
-// no quick menu for now
 pub struct LauncherWidget {
     button: Button,
 }
 
 impl LauncherWidget {
-    pub fn new() -> Self {
-        let button = Button::builder().label("").build();
+    pub fn new(config: LauncherConfig) -> Self {
+        let button = Button::new();
         button.set_widget_name("launcher");
+        button.set_child(Some(&create_icon(&config)));
+        if !config.tooltip.is_empty() {
+            button.set_tooltip_text(Some(&config.tooltip));
+        }
 
-        button.connect_clicked(|_| {
-            glib::spawn_future_local(async move {
-                if let Err(e) = Command::new("sh").args(["-c", "better-launcher"]).spawn() {
-                    eprintln!("Failed to launch better-launcher: {}", e);
-                }
-            });
+        let config = Rc::new(config);
+
+        let menu = config.menu.then(|| create_app_menu(&button));
+        let config_clone = config.clone();
+        button.connect_clicked(move |button| match &menu {
+            Some(menu) if menu.is_visible() => menu.popdown(),
+            Some(menu) => menu.popup(),
+            None => launch(button, &config_clone.command, config_clone.clone()),
         });
 
+        // Right and middle click, claimed on press so the bar menu does not open
+        let gesture = GestureClick::new();
+        gesture.set_button(0);
+        gesture.connect_pressed(|gesture, _, _, _| {
+            if gesture.current_button() != gdk::BUTTON_PRIMARY {
+                gesture.set_state(gtk::EventSequenceState::Claimed);
+            }
+        });
+        gesture.connect_released(move |gesture, _, _, _| {
+            let command = match gesture.current_button() {
+                gdk::BUTTON_SECONDARY => &config.right_command,
+                gdk::BUTTON_MIDDLE => &config.middle_command,
+                _ => return,
+            };
+            if let Some(button) = gesture.widget().and_downcast::<Button>() {
+                launch(&button, command, config.clone());
+            }
+        });
+        button.add_controller(gesture);
+
         Self { button }
     }
 
@@ -29,3 +64,31 @@ impl LauncherWidget {
         &self.button
     }
 }
+
+// Errors stay in the tooltip until a command runs fine
+fn launch(button: &Button, command: &str, config: Rc<LauncherConfig>) {
+    if command.trim().is_empty() {
+        return;
+    }
+    let button = button.clone();
+    let command = command.to_string();
+    glib::spawn_future_local(async move {
+        let error = match run_command(&command, &config).await {
+            Ok(status) => describe_failure(&command, status),
+            Err(e) => Some(e),
+        };
+
+        match error {
+            Some(error) => {
+                LOG.error(&error);
+                button.add_css_class("error");
+                button.set_tooltip_text(Some(&error));
+            }
+            None => {
+                button.remove_css_class("error");
+                let tooltip = Some(config.tooltip.as_str()).filter(|tooltip| !tooltip.is_empty());
+                button.set_tooltip_text(tooltip);
+            }
+        }
+    });
+}
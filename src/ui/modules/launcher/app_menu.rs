@@ -0,0 +1,233 @@
+// App menu popover with categories and fuzzy search
+use gtk::{gdk, glib, prelude::*};
+use gtk::{
+    Align, Box as GtkBox, EventControllerKey, Image, Label, ListBox, ListBoxRow, Orientation, Popover,
+    PolicyType, ScrolledWindow, SearchEntry, SelectionMode,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::ui::bar::set_keyboard_focus;
+use crate::ui::modules::launcher::app_menu_helper::{load_apps, search, used_categories, AppEntry};
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("app_menu",LogLevel::Debug);
+}
+
+const ALL_CATEGORY: &str = "All";
+
+struct MenuState {
+    apps: Vec<AppEntry>,
+    category: &'static str,
+    shown: Vec<AppEntry>, // app of each row in the list
+}
+
+// Popover attached to `parent`, the caller pops it up
+pub fn create_app_menu(parent: &impl IsA<gtk::Widget>) -> Popover {
+    let popover = Popover::new();
+    popover.set_parent(parent);
+    popover.set_autohide(true);
+
+    let content = GtkBox::new(Orientation::Vertical, 6);
+    content.set_widget_name("app-menu");
+
+    let search_entry = SearchEntry::new();
+    search_entry.set_placeholder_text(Some("Search applications"));
+    content.append(&search_entry);
+
+    let body = GtkBox::new(Orientation::Horizontal, 6);
+    let categories = ListBox::new();
+    categories.set_selection_mode(SelectionMode::Single);
+    categories.add_css_class("app-menu-categories");
+    body.append(&categories);
+
+    let list = ListBox::new();
+    list.set_selection_mode(SelectionMode::Single);
+    list.add_css_class("app-menu-apps");
+    let scrolled = ScrolledWindow::new();
+    scrolled.set_policy(PolicyType::Never, PolicyType::Automatic);
+    scrolled.set_min_content_height(380);
+    scrolled.set_min_content_width(280);
+    scrolled.set_child(Some(&list));
+    body.append(&scrolled);
+    content.append(&body);
+    popover.set_child(Some(&content));
+
+    let state = Rc::new(RefCell::new(MenuState {
+        apps: Vec::new(),
+        category: ALL_CATEGORY,
+        shown: Vec::new(),
+    }));
+
+    // Search results replace the category view while there is a query
+    let refresh = {
+        let state = state.clone();
+        let list = list.clone();
+        let search_entry = search_entry.clone();
+        Rc::new(move || {
+            let mut state = state.borrow_mut();
+            let query = search_entry.text();
+            let shown: Vec<AppEntry> = if query.trim().is_empty() {
+                let category = state.category;
+                state
+                    .apps
+                    .iter()
+                    .filter(|app| category == ALL_CATEGORY || app.category == category)
+                    .cloned()
+                    .collect()
+            } else {
+                search(&state.apps, &query).into_iter().cloned().collect()
+            };
+            fill_apps(&list, &shown);
+            state.shown = shown;
+        })
+    };
+
+    let refresh_clone = refresh.clone();
+    search_entry.connect_search_changed(move |_| refresh_clone());
+
+    let state_clone = state.clone();
+    let refresh_clone = refresh.clone();
+    let search_clone = search_entry.clone();
+    categories.connect_row_selected(move |_, row| {
+        let Some(row) = row else {
+            return;
+        };
+        let name = row.child().and_downcast::<Label>().map(|label| label.text().to_string());
+        let category = used_categories(&state_clone.borrow().apps)
+            .into_iter()
+            .find(|category| Some(*category) == name.as_deref())
+            .unwrap_or(ALL_CATEGORY);
+        state_clone.borrow_mut().category = category;
+        // picking a category leaves the search
+        if !search_clone.text().is_empty() {
+            search_clone.set_text("");
+        } else {
+            refresh_clone();
+        }
+    });
+
+    // Launching
+    let launch = {
+        let state = state.clone();
+        let popover = popover.clone();
+        Rc::new(move |index: usize| {
+            let Some(entry) = state.borrow().shown.get(index).cloned() else {
+                return;
+            };
+            popover.popdown();
+            launch_app(&entry, &popover);
+        })
+    };
+
+    let launch_clone = launch.clone();
+    list.connect_row_activated(move |_, row| launch_clone(row.index() as usize));
+
+    let launch_clone = launch.clone();
+    let list_clone = list.clone();
+    search_entry.connect_activate(move |_| {
+        let index = list_clone.selected_row().map(|row| row.index()).unwrap_or(0);
+        launch_clone(index as usize);
+    });
+
+    // Arrow keys move through the results while typing stays in the search
+    let keys = EventControllerKey::new();
+    let list_clone = list.clone();
+    let scrolled_clone = scrolled.clone();
+    keys.connect_key_pressed(move |_, key, _, _| {
+        let step = match key {
+            gdk::Key::Down => 1,
+            gdk::Key::Up => -1,
+            _ => return glib::Propagation::Proceed,
+        };
+        let index = list_clone.selected_row().map(|row| row.index()).unwrap_or(-1) + step;
+        if let Some(row) = list_clone.row_at_index(index.max(0)) {
+            list_clone.select_row(Some(&row));
+            scroll_to_row(&scrolled_clone, &list_clone, &row);
+        }
+        glib::Propagation::Stop
+    });
+    search_entry.add_controller(keys);
+
+    // Keyboard focus is only taken while the menu is open
+    let state_clone = state.clone();
+    let search_clone = search_entry.clone();
+    popover.connect_show(move |popover| {
+        set_keyboard_focus(popover, true);
+
+        // the index is rebuilt on each open so new installs show up
+        let apps = load_apps();
+        fill_categories(&categories, &used_categories(&apps));
+        {
+            let mut state = state_clone.borrow_mut();
+            state.apps = apps;
+            state.category = ALL_CATEGORY;
+        }
+        if let Some(row) = categories.row_at_index(0) {
+            categories.select_row(Some(&row));
+        }
+        search_clone.set_text("");
+        refresh();
+        search_clone.grab_focus();
+    });
+    popover.connect_closed(|popover| set_keyboard_focus(popover, false));
+
+    popover
+}
+
+fn scroll_to_row(scrolled: &ScrolledWindow, list: &ListBox, row: &ListBoxRow) {
+    if let Some(bounds) = row.compute_bounds(list) {
+        let top = bounds.y() as f64;
+        scrolled.vadjustment().clamp_page(top, top + bounds.height() as f64);
+    }
+}
+
+fn launch_app(entry: &AppEntry, popover: &Popover) {
+    // DesktopAppInfo expands field codes and runs Terminal=true apps in a terminal
+    let context = popover.display().app_launch_context();
+    LOG.debug(&format!("Launching {}", entry.name));
+    if let Err(e) = entry.app.launch(&[], Some(&context)) {
+        LOG.error(&format!("Failed to launch {}: {}", entry.name, e));
+    }
+}
+
+fn fill_categories(categories: &ListBox, names: &[&'static str]) {
+    categories.remove_all();
+    for name in [ALL_CATEGORY].iter().chain(names) {
+        let label = Label::new(Some(name));
+        label.set_halign(Align::Start);
+        categories.append(&label);
+    }
+}
+
+fn fill_apps(list: &ListBox, apps: &[AppEntry]) {
+    list.remove_all();
+    for entry in apps {
+        let row_box = GtkBox::new(Orientation::Horizontal, 8);
+
+        let icon = match entry.app.icon() {
+            Some(icon) => Image::from_gicon(&icon),
+            None => Image::from_icon_name("application-x-executable"),
+        };
+        icon.set_pixel_size(24);
+        row_box.append(&icon);
+
+        let name = Label::new(Some(&entry.name));
+        name.set_halign(Align::Start);
+        name.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        row_box.append(&name);
+
+        let row = ListBoxRow::new();
+        row.set_child(Some(&row_box));
+        if let Some(description) = entry.app.description() {
+            row.set_tooltip_text(Some(&description));
+        }
+        list.append(&row);
+    }
+
+    if let Some(row) = list.row_at_index(0) {
+        list.select_row(Some(&row));
+    }
+}
@@ -0,0 +1,113 @@
+// Application index for the app menu, read from the .desktop files in $XDG_DATA_DIRS
+use gio::{prelude::*, AppInfo, DesktopAppInfo};
+
+// Main categories of the desktop menu spec, in menu order
+pub const CATEGORIES: [(&str, &str); 11] = [
+    ("AudioVideo", "Multimedia"),
+    ("Development", "Development"),
+    ("Education", "Education"),
+    ("Game", "Games"),
+    ("Graphics", "Graphics"),
+    ("Network", "Internet"),
+    ("Office", "Office"),
+    ("Science", "Science"),
+    ("Settings", "Settings"),
+    ("System", "System"),
+    ("Utility", "Accessories"),
+];
+pub const OTHER_CATEGORY: &str = "Other";
+
+#[derive(Clone)]
+pub struct AppEntry {
+    pub app: DesktopAppInfo,
+    pub name: String,
+    pub category: &'static str, // display name from CATEGORIES or OTHER_CATEGORY
+    search_terms: Vec<String>,  // lowercase generic name, keywords and executable
+}
+
+// Apps that should be shown in this desktop, sorted by name
+// should_show() covers NoDisplay, Hidden, OnlyShowIn and NotShowIn
+pub fn load_apps() -> Vec<AppEntry> {
+    let mut apps: Vec<AppEntry> = AppInfo::all()
+        .into_iter()
+        .filter(|app| app.should_show())
+        .filter_map(|app| app.downcast::<DesktopAppInfo>().ok())
+        .map(|app| {
+            let mut search_terms: Vec<String> = app.keywords().iter().map(|k| k.to_lowercase()).collect();
+            if let Some(generic) = app.generic_name() {
+                search_terms.push(generic.to_lowercase());
+            }
+            if let Some(name) = app.executable().file_name() {
+                search_terms.push(name.to_string_lossy().to_lowercase());
+            }
+
+            AppEntry {
+                name: app.display_name().to_string(),
+                category: main_category(app.categories().as_deref().unwrap_or_default()),
+                search_terms,
+                app,
+            }
+        })
+        .collect();
+
+    apps.sort_by_cached_key(|entry| entry.name.to_lowercase());
+    apps
+}
+
+// "GTK;Development;IDE;" -> "Development"
+fn main_category(categories: &str) -> &'static str {
+    categories
+        .split(';')
+        .find_map(|category| CATEGORIES.iter().find(|(id, _)| *id == category).map(|(_, name)| *name))
+        .unwrap_or(OTHER_CATEGORY)
+}
+
+// Categories that have apps, in menu order
+pub fn used_categories(apps: &[AppEntry]) -> Vec<&'static str> {
+    CATEGORIES
+        .iter()
+        .map(|(_, name)| *name)
+        .chain([OTHER_CATEGORY])
+        .filter(|name| apps.iter().any(|app| app.category == *name))
+        .collect()
+}
+
+// Characters of the query in order, bonus for consecutive matches and word starts
+// None when the text does not contain the query
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (position..text.len()).find(|&i| text[i] == c)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 8;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+
+    // shorter names that match are usually what was meant
+    Some(score * 10 - text.len() as i32)
+}
+
+// Best matches first, names count more than keywords
+pub fn search<'a>(apps: &'a [AppEntry], query: &str) -> Vec<&'a AppEntry> {
+    let mut matches: Vec<(i32, &AppEntry)> = apps
+        .iter()
+        .filter_map(|entry| {
+            let name = fuzzy_score(query, &entry.name).map(|score| score * 2);
+            let terms = entry.search_terms.iter().filter_map(|term| fuzzy_score(query, term)).max();
+            name.max(terms).map(|score| (score, entry))
+        })
+        .collect();
+
+    matches.sort_by_key(|(score, entry)| (std::cmp::Reverse(*score), entry.name.to_lowercase()));
+    matches.into_iter().map(|(_, entry)| entry).collect()
+}
@@ -0,0 +1,56 @@
+// Launcher icon and commands
+use gtk::{prelude::*, Image, Label, Widget};
+use std::process::ExitStatus;
+use tokio::process::Command;
+
+use crate::config::config::LauncherConfig;
+use crate::utils::paths::expand_home;
+
+// Image path, icon name from the theme, or text such as a nerd font glyph
+pub fn create_icon(config: &LauncherConfig) -> Widget {
+    let icon = config.icon.trim();
+
+    if icon.starts_with('/') || icon.starts_with('~') {
+        let image = Image::from_file(expand_home(icon));
+        image.set_pixel_size(config.icon_size);
+        return image.upcast();
+    }
+
+    let has_icon = gtk::gdk::Display::default()
+        .map(|display| gtk::IconTheme::for_display(&display).has_icon(icon))
+        .unwrap_or(false);
+    if !icon.is_empty() && has_icon {
+        let image = Image::from_icon_name(icon);
+        image.set_pixel_size(config.icon_size);
+        return image.upcast();
+    }
+
+    Label::new(Some(icon)).upcast()
+}
+
+// Run a command with the configured directory and environment, resolves when it exits
+pub async fn run_command(command: &str, config: &LauncherConfig) -> Result<ExitStatus, String> {
+    let mut process = Command::new("sh");
+    process.args(["-c", command]).envs(&config.env);
+
+    if !config.working_dir.is_empty() {
+        let dir = expand_home(&config.working_dir);
+        if !dir.is_dir() {
+            return Err(format!("Working directory not found: {}", dir.display()));
+        }
+        process.current_dir(dir);
+    }
+
+    let mut child = process.spawn().map_err(|e| format!("Failed to run '{}': {}", command, e))?;
+    child.wait().await.map_err(|e| format!("Failed to wait for '{}': {}", command, e))
+}
+
+// Message for exit codes that mean the command itself was not usable
+pub fn describe_failure(command: &str, status: ExitStatus) -> Option<String> {
+    let program = command.split_whitespace().next().unwrap_or(command);
+    match status.code() {
+        Some(127) => Some(format!("Command not found: {}", program)),
+        Some(126) => Some(format!("Command not executable: {}", program)),
+        _ => None,
+    }
+}
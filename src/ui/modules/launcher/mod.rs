@@ -1 +1,4 @@
-pub mod app_launcher;
\ No newline at end of file
+pub mod app_launcher;
+pub mod app_menu;
+pub mod app_menu_helper;
+pub mod launcher_helper;
\ No newline at end of file
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 
 use lazy_static::lazy_static;
 
+use crate::utils::format::format_size;
 use crate::utils::logger::{LogLevel, Logger};
 
 lazy_static! {
@@ -17,6 +18,17 @@ pub struct MemoryStats {
     pub _available: i64,
     pub _used: i64,
     pub _zfs_size: i64,
+    pub swap_total: i64,
+    pub swap_free: i64,
+    pub zram: Option<ZramStats>,
+}
+
+// Summed over all /sys/block/zram* devices, in kB
+#[derive(Debug, Clone, Default)]
+pub struct ZramStats {
+    pub original: i64,   // uncompressed data stored
+    pub compressed: i64, // compressed size of the data
+    pub used: i64,       // memory used including allocator overhead
 }
 
 impl MemoryStats {
@@ -32,21 +44,37 @@ impl MemoryStats {
         (self._available as f64 / self._total as f64) * 100.0
     }
 
+    pub fn swap_used(&self) -> i64 {
+        self.swap_total - self.swap_free
+    }
+
+    pub fn swap_percentage(&self) -> f64 {
+        if self.swap_total == 0 {
+            return 0.0;
+        }
+        (self.swap_used() as f64 / self.swap_total as f64) * 100.0
+    }
+
     pub fn format_bytes(kb: i64) -> String {
-        let gb = kb as f64 / 1024.0 / 1024.0;
-        format!("{:.1} GB", gb)
+        format_size(kb.max(0) as u64 * 1024)
     }
 }
 
-pub struct MemoryMonitor;
+pub struct MemoryMonitor {
+    include_zfs_arc: bool, // count ZFS ARC as used memory
+}
 
 impl MemoryMonitor {
-    pub fn new() -> Self {
-        Self
+    pub fn new(include_zfs_arc: bool) -> Self {
+        Self { include_zfs_arc }
     }
 
     pub fn get_memory_stats(&mut self) -> Result<MemoryStats, String> {
-        let info = parse_meminfo();
+        let mut info = parse_meminfo();
+        if self.include_zfs_arc {
+            // Insert ZFS ARC size under key "zfs_size"
+            info.insert("zfs_size".to_string(), zfs_arc_size() as i64);
+        }
 
         let total = match info.get("MemTotal") {
             Some(val) => *val,
@@ -63,13 +91,7 @@ impl MemoryMonitor {
                 0
             }
         };
-        let zfs = match info.get("zfs_size") {
-            Some(val) => *val,
-            None => {
-                LOG.error("memory: Failed to get ZFS ARC size");
-                0
-            }
-        };
+        let zfs = info.get("zfs_size").copied().unwrap_or(0);
         let used = total - available + zfs;
 
         Ok(MemoryStats {
@@ -77,6 +99,9 @@ impl MemoryMonitor {
             _available: available,
             _used: used,
             _zfs_size: zfs,
+            swap_total: info.get("SwapTotal").copied().unwrap_or(0),
+            swap_free: info.get("SwapFree").copied().unwrap_or(0),
+            zram: zram_stats(),
         })
     }
 
@@ -87,10 +112,41 @@ impl MemoryMonitor {
             _available: 0,
             _used: 0,
             _zfs_size: 0,
+            swap_total: 0,
+            swap_free: 0,
+            zram: None,
         }
     }
 }
 
+// Reads mm_stat of every zram device, None without zram
+fn zram_stats() -> Option<ZramStats> {
+    let mut stats: Option<ZramStats> = None;
+
+    for entry in fs::read_dir("/sys/block").ok()?.flatten() {
+        if !entry.file_name().to_string_lossy().starts_with("zram") {
+            continue;
+        }
+        // orig_data_size compr_data_size mem_used_total ... in bytes
+        let Ok(content) = fs::read_to_string(entry.path().join("mm_stat")) else {
+            continue;
+        };
+        let values: Vec<i64> = content
+            .split_whitespace()
+            .take(3)
+            .filter_map(|v| v.parse().ok())
+            .collect();
+        if let [original, compressed, used] = values[..] {
+            let total = stats.get_or_insert_with(ZramStats::default);
+            total.original += original / 1024;
+            total.compressed += compressed / 1024;
+            total.used += used / 1024;
+        }
+    }
+
+    stats
+}
+
 fn zfs_arc_size() -> u64 {
     LOG.debug("memory: passing /proc/spl/kstat/zfs/arcstats");
     let file = File::open("/proc/spl/kstat/zfs/arcstats");
@@ -115,7 +171,7 @@ fn zfs_arc_size() -> u64 {
     0
 }
 
-/// Parses /proc/meminfo and adds zfs_size if available.
+/// Parses /proc/meminfo
 fn parse_meminfo() -> HashMap<String, i64> {
     LOG.debug("memory: passing /proc/meminfo");
     let file = match File::open("/proc/meminfo") {
@@ -140,7 +196,5 @@ fn parse_meminfo() -> HashMap<String, i64> {
         }
     }
 
-    // Insert ZFS ARC size under key "zfs_size"
-    meminfo.insert("zfs_size".to_string(), zfs_arc_size() as i64);
     meminfo
 }
@@ -1,30 +1,49 @@
 use std::time::Duration;
 
 use gtk::{prelude::*, Label,};
+use crate::config::config::MemoryConfig;
+use crate::config::config_helper::get_config;
 use crate::ui::modules::memory::memory::{MemoryMonitor, MemoryStats};
 
 #[derive(Debug, Clone, Copy)]
 pub enum MemoryDisplayMode {
-    _UsedOnly,           // SHows used only memory
-    _UsedWithTotal,      // Shows as 4gb/12gb
-    _UsedPercentage,     // Shows memory usage percentage
-    _Available,          // Shows available memory
-    _AvailablePercentage, // Shows available memory in percentage
-    _Detailed,           // Shows memory in detail eg: "Used: 8.2 gb, Available: 7.8 gb"
+    UsedOnly,            // SHows used only memory
+    UsedWithTotal,       // Shows as 4gb/12gb
+    UsedPercentage,      // Shows memory usage percentage
+    Available,           // Shows available memory
+    AvailablePercentage, // Shows available memory in percentage
+    Detailed,            // Shows memory in detail eg: "Used: 8.2 gb, Available: 7.8 gb"
+    Custom,              // Uses format from config
 }
 
-// Shows used only memory
-pub fn _create_memory_widget() -> Label {
-    create_memory_widget_with_mode(MemoryDisplayMode::_UsedOnly)
+impl MemoryDisplayMode {
+    // Mode from the `display` config value, percentage if unknown
+    pub fn from_config(display: &str) -> Self {
+        match display {
+            "used" => Self::UsedOnly,
+            "used_total" => Self::UsedWithTotal,
+            "available" => Self::Available,
+            "available_percentage" => Self::AvailablePercentage,
+            "detailed" => Self::Detailed,
+            "custom" => Self::Custom,
+            _ => Self::UsedPercentage,
+        }
+    }
+}
+
+// Memory widget using the display mode from config
+pub fn create_memory_widget() -> Label {
+    let config = get_config().unwrap().memory.clone();
+    create_memory_widget_with_mode(MemoryDisplayMode::from_config(&config.display), config)
 }
 
 // Memory widget with different display modes
-pub fn create_memory_widget_with_mode(mode: MemoryDisplayMode) -> Label {
+pub fn create_memory_widget_with_mode(mode: MemoryDisplayMode, config: MemoryConfig) -> Label {
     let label = Label::new(Some("RAM: 0 MB"));
     let label_clone = label.clone();
-    let mut memory_monitor = MemoryMonitor::new();
+    let mut memory_monitor = MemoryMonitor::new(config.include_zfs_arc);
     
-    glib::timeout_add_local(Duration::from_secs(2), move || {
+    glib::timeout_add_local(Duration::from_secs(config.interval.max(1) as u64), move || {
         if !label_clone.is_visible() {
             return glib::ControlFlow::Break;
         }
@@ -34,8 +53,11 @@ pub fn create_memory_widget_with_mode(mode: MemoryDisplayMode) -> Label {
             Err(err) => memory_monitor.handle_error(err),
         };
 
-        let text = _format_memory_display(&stats, mode);
+        let text = _format_memory_display(&stats, mode, &config);
         label_clone.set_text(&text);
+        if config.tooltip {
+            label_clone.set_tooltip_markup(Some(&format_memory(&config.tooltip_format, &stats)));
+        }
         
         // Update CSS classes
         label_clone.remove_css_class("low");
@@ -44,8 +66,8 @@ pub fn create_memory_widget_with_mode(mode: MemoryDisplayMode) -> Label {
 
         let usage_percent = stats.used_percentage();
         let css_class = match usage_percent {
-            u if u < 50.0 => "low",
-            u if u < 80.0 => "medium", 
+            u if u < config.medium_threshold => "low",
+            u if u < config.high_threshold => "medium", 
             _ => "high",
         };
         label_clone.add_css_class(css_class);
@@ -56,51 +78,50 @@ pub fn create_memory_widget_with_mode(mode: MemoryDisplayMode) -> Label {
     label
 }
 
+// Replace memory placeholders, see MemoryConfig
+pub fn format_memory(format: &str, stats: &MemoryStats) -> String {
+    let zram = stats.zram.clone().unwrap_or_default();
+    format
+        .replace("{used}", &MemoryStats::format_bytes(stats.used()))
+        .replace("{total}", &MemoryStats::format_bytes(stats._total))
+        .replace("{available}", &MemoryStats::format_bytes(stats._available))
+        .replace("{percentage}", &format!("{}", stats.used_percentage() as u32))
+        .replace("{swap_used}", &MemoryStats::format_bytes(stats.swap_used()))
+        .replace("{swap_total}", &MemoryStats::format_bytes(stats.swap_total))
+        .replace("{swap_percentage}", &format!("{}", stats.swap_percentage() as u32))
+        .replace("{zram_used}", &MemoryStats::format_bytes(zram.used))
+        .replace("{zram_original}", &MemoryStats::format_bytes(zram.original))
+        .replace("{zram_compressed}", &MemoryStats::format_bytes(zram.compressed))
+}
 
 // Helper function to format memory display based on mode
 // Added this to show different memory informations
-fn _format_memory_display(stats: &MemoryStats, mode: MemoryDisplayMode) -> String {
+fn _format_memory_display(stats: &MemoryStats, mode: MemoryDisplayMode, config: &MemoryConfig) -> String {
     match mode {
-        MemoryDisplayMode::_UsedOnly => {
+        MemoryDisplayMode::UsedOnly => {
             format!("RAM: {}", MemoryStats::format_bytes(stats.used()))
         }
-        MemoryDisplayMode::_UsedWithTotal => {
+        MemoryDisplayMode::UsedWithTotal => {
             format!("RAM: {} / {}", 
                 MemoryStats::format_bytes(stats.used()),
                 MemoryStats::format_bytes(stats._total)
             )
         }
-        MemoryDisplayMode::_UsedPercentage => {
+        MemoryDisplayMode::UsedPercentage => {
             format!("RAM: {}%", stats.used_percentage() as u32)
         }
-        MemoryDisplayMode::_Available => {
+        MemoryDisplayMode::Available => {
             format!("RAM: {} available", MemoryStats::format_bytes(stats._available))
         }
-        MemoryDisplayMode::_AvailablePercentage => {
+        MemoryDisplayMode::AvailablePercentage => {
             format!("RAM: {}% available", stats.available_percentage() as u32)
         }
-        MemoryDisplayMode::_Detailed => {
+        MemoryDisplayMode::Detailed => {
             format!("Used: {}, Available: {}", 
                 MemoryStats::format_bytes(stats.used()),
                 MemoryStats::format_bytes(stats._available)
             )
         }
+        MemoryDisplayMode::Custom => format_memory(&config.format, stats),
     }
 }
-
-// Functions for different display modes
-pub fn _create_memory_widget_used_only() -> Label {
-    create_memory_widget_with_mode(MemoryDisplayMode::_UsedOnly)
-}
-
-pub fn _create_memory_widget_with_total() -> Label {
-    create_memory_widget_with_mode(MemoryDisplayMode::_UsedWithTotal)
-}
-
-pub fn _create_memory_widget_percentage() -> Label {
-    create_memory_widget_with_mode(MemoryDisplayMode::_UsedPercentage)
-}
-
-pub fn _create_memory_widget_available() -> Label {
-    create_memory_widget_with_mode(MemoryDisplayMode::_Available)
-}
\ No newline at end of file
@@ -6,4 +6,12 @@ pub mod battery;
 pub mod network;
 pub mod launcher;
 pub mod cpu;
-pub mod memory;
\ No newline at end of file
+pub mod memory;
+pub mod processes;
+pub mod temperature;
+pub mod disk;
+pub mod fan;
+pub mod pinned_apps;
+pub mod power;
+pub mod idle_inhibitor;
+pub mod backlight;
\ No newline at end of file
@@ -2,6 +2,7 @@ use std::{fs, process::Command};
 use anyhow::Result;
 
 use crate::ui::modules::network::network::NetworkInfo;
+use crate::utils::format::format_rate;
 
 pub fn get_ip_address(interface: &str) -> Result<String> {
     let output = Command::new("ip")
@@ -113,15 +114,7 @@ pub fn get_network_speeds(interface: &str) -> (String, String) {
 }
 
 pub fn format_bytes(bytes: u64) -> String {
-    if bytes < 1024 {
-        format!("{} B/s", bytes)
-    } else if bytes < 1024 * 1024 {
-        format!("{:.1} KB/s", bytes as f64 / 1024.0)
-    } else if bytes < 1024 * 1024 * 1024 {
-        format!("{:.1} MB/s", bytes as f64 / (1024.0 * 1024.0))
-    } else {
-        format!("{:.1} GB/s", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
-    }
+    format_rate(bytes)
 }
 
 
@@ -3,17 +3,28 @@ use gtk::{
     PopoverMenu, prelude::*,
 };
 
-use super::clock::ClockModule;
+use super::clock::clock::ClockModule;
 use crate::{
     config::config::Config,
     system::{
         global::_is_hyprland_session, system_info_modules::SystemInfoModule, updater::SystemUpdater,
     },
     ui::modules::{
+        backlight::backlight::Backlight,
         battery::battery::Battery,
-        hyprland::{window::window_title::WindowWidget, workspace::workspaces::WorkspaceWidget},
+        disk::disk::Disk,
+        fan::fan::Fan,
+        hyprland::{
+            keyboard::keyboard_layout::KeyboardLayoutWidget, submap::submap::SubmapWidget,
+            taskbar::taskbar::TaskbarWidget, window::window_title::WindowWidget,
+            workspace::workspaces::WorkspaceWidget,
+        },
         launcher::app_launcher::LauncherWidget,
+        idle_inhibitor::idle_inhibitor::IdleInhibitor,
         network::network::Network,
+        pinned_apps::pinned_apps::PinnedAppsWidget,
+        power::power::PowerMenu,
+        temperature::temperature::Temperature,
         volume::volume::Volume,
     },
 };
@@ -28,9 +39,12 @@ pub struct PanelState {
     pub _launcher: Rc<LauncherWidget>,
     pub _workspace_widget: Option<Rc<WorkspaceWidget>>,
     pub _window_title: Option<Rc<WindowWidget>>,
-    pub _time_label: Label,
-    pub _cpu_label: Option<Label>,
-    // pub _memory_label: Label,
+    pub _taskbar: Option<Rc<TaskbarWidget>>,
+    pub _submap: Option<Rc<SubmapWidget>>,
+    pub _keyboard_layout: Option<Rc<KeyboardLayoutWidget>>,
+    pub _clock: Rc<ClockModule>,
+    pub _cpu_widget: Option<GtkBox>,
+    pub _memory_label: Option<Label>,
 }
 
 impl PanelState {
@@ -57,7 +71,8 @@ impl PanelBuilder {
         Self
     }
 
-    pub fn build(&self, window: &ApplicationWindow) -> PanelState {
+    /// `output` is the monitor connector name the bar is shown on
+    pub fn build(&self, window: &ApplicationWindow, output: Option<String>) -> PanelState {
         let config = get_config().unwrap();
 
         let main_box = CenterBox::new();
@@ -76,26 +91,45 @@ impl PanelBuilder {
         let left_box = GtkBox::new(Orientation::Horizontal, 0);
         left_box.set_halign(Align::Start);
 
-        let _launcher = Rc::new(LauncherWidget::new());
+        let _launcher = Rc::new(LauncherWidget::new(config.launcher.clone()));
         left_box.append(_launcher.widget());
 
+        if config.modules.pinned_apps {
+            let pinned_apps = PinnedAppsWidget::new(config.pinned_apps.clone());
+            left_box.append(pinned_apps.widget());
+        }
+
         // Show only for hyprland session
         let mut _workspace_widget: Option<Rc<WorkspaceWidget>> = None;
         let mut _window_title: Option<Rc<WindowWidget>> = None;
+        let mut _taskbar: Option<Rc<TaskbarWidget>> = None;
+        let mut _submap: Option<Rc<SubmapWidget>> = None;
 
         // Only try to initialize them if we're in a Hyprland session
         if _is_hyprland_session() {
             if config.modules.workspaces {
-                let widget = Rc::new(WorkspaceWidget::new());
+                let widget = Rc::new(WorkspaceWidget::new(output.clone(), config.workspaces.clone()));
                 left_box.append(widget.widget());
                 _workspace_widget = Some(widget);
             }
 
+            if config.modules.submap {
+                let widget = Rc::new(SubmapWidget::new(config.submap.clone()));
+                left_box.append(widget.widget());
+                _submap = Some(widget);
+            }
+
             if config.modules.window_title {
-                let widget = Rc::new(WindowWidget::new());
+                let widget = Rc::new(WindowWidget::new(config.window_title.clone()));
                 left_box.append(widget.widget());
                 _window_title = Some(widget);
             }
+
+            if config.modules.taskbar {
+                let widget = Rc::new(TaskbarWidget::new(output, config.taskbar.clone()));
+                left_box.append(widget.widget());
+                _taskbar = Some(widget);
+            }
         }
 
         // Center section
@@ -103,9 +137,8 @@ impl PanelBuilder {
         center_box.set_halign(Align::Center);
         center_box.set_hexpand(true);
 
-        let clock_module = ClockModule::new();
-        let _time_label = clock_module.create();
-        center_box.append(&_time_label);
+        let _clock = Rc::new(ClockModule::new(config.clock.clone(), config.timer.clone(), config.alarm.clone()));
+        center_box.append(&_clock.create());
 
         // Right section
         let right_box = GtkBox::new(Orientation::Horizontal, 0);
@@ -118,14 +151,42 @@ impl PanelBuilder {
         add_gesture_blocker(&center_box);
         add_gesture_blocker(&right_box);
 
+        let mut _keyboard_layout: Option<Rc<KeyboardLayoutWidget>> = None;
+        if _is_hyprland_session() && config.modules.keyboard_layout {
+            let widget = Rc::new(KeyboardLayoutWidget::new(config.keyboard_layout.clone()));
+            right_box.append(widget.widget());
+            _keyboard_layout = Some(widget);
+        }
+
         let system_info = SystemInfoModule::new();
-        let _cpu_label = if let Some(cpu) = system_info.create(&right_box) {
-            Some(cpu)
-        } else {
-            None
-        };
+        let system_info_widgets = system_info.create(&right_box);
+        let _cpu_widget = system_info_widgets.cpu;
+        let _memory_label = system_info_widgets.memory;
 
         
+        if config.modules.idle_inhibitor {
+            let idle_inhibitor = IdleInhibitor::new(config.idle_inhibitor.clone());
+            right_box.append(idle_inhibitor.widget());
+        }
+
+        if config.modules.disk {
+            let disk = Disk::new(config.disk.clone());
+            right_box.append(disk.widget());
+            disk.start_updates();
+        }
+
+        if config.modules.temperature {
+            let temperature = Temperature::new(config.temperature.clone());
+            right_box.append(temperature.widget());
+            temperature.start_updates();
+        }
+
+        if config.modules.fan {
+            let fan = Fan::new(config.fan.clone(), config.temperature.clone());
+            right_box.append(fan.widget());
+            fan.start_updates();
+        }
+
         let network_config = Config::load().unwrap().network;
         let network = Network::new(network_config.clone());
         right_box.append(network.widget());
@@ -134,6 +195,12 @@ impl PanelBuilder {
             network.start_updates();
         }
 
+        if config.modules.backlight {
+            let backlight = Backlight::new(config.backlight.clone());
+            right_box.append(backlight.widget());
+            backlight.start_updates();
+        }
+
         let battery_config = Config::load().unwrap().battery;
         let battery = Battery::new(battery_config.clone());
         right_box.append(battery.widget());
@@ -150,6 +217,11 @@ impl PanelBuilder {
             volume.start_updates();
         }
 
+        if config.modules.power {
+            let power = PowerMenu::new(config.power.clone());
+            right_box.append(power.widget());
+        }
+
         main_box.set_start_widget(Some(&left_box));
         main_box.set_center_widget(Some(&center_box));
         main_box.set_end_widget(Some(&right_box));
@@ -159,9 +231,12 @@ impl PanelBuilder {
             _launcher,
             _workspace_widget,
             _window_title,
-            _time_label,
-            // _memory_label,
-            _cpu_label,
+            _taskbar,
+            _submap,
+            _keyboard_layout,
+            _clock,
+            _memory_label,
+            _cpu_widget,
         }
     }
 }
@@ -0,0 +1,2 @@
+pub mod pinned_apps;
+pub mod pinned_apps_helper;
\ No newline at end of file
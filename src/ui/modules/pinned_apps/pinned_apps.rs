@@ -0,0 +1,199 @@
+// Pinned apps module
+use gio::prelude::*;
+use gio::DesktopAppInfo;
+use gtk::{gdk, prelude::*, Box, Button, GestureClick, Image, Orientation};
+use hyprland::data::{Client, Clients};
+use hyprland::dispatch::{Dispatch, DispatchType, WindowIdentifier};
+use hyprland::shared::{Address, HyprData, HyprDataActiveOptional, HyprDataVec};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::config::config::PinnedAppsConfig;
+use crate::system::global::_is_hyprland_session;
+use crate::ui::modules::hyprland::event_helper::{attach_events, spawn_event_listener};
+use crate::ui::modules::pinned_apps::pinned_apps_helper::{app_windows, resolve_app, window_to_focus, AppWindow};
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("pinned_apps",LogLevel::Debug);
+}
+
+struct PinnedApp {
+    app: DesktopAppInfo,
+    button: Button,
+}
+
+#[derive(Clone)]
+pub struct PinnedAppsWidget {
+    container: Box,
+    config: Rc<PinnedAppsConfig>,
+    apps: Rc<Vec<PinnedApp>>,
+    windows: Rc<RefCell<HashMap<String, Vec<AppWindow>>>>, // desktop id -> open windows
+    active: Rc<RefCell<Option<Address>>>,
+}
+
+impl PinnedAppsWidget {
+    pub fn new(config: PinnedAppsConfig) -> Self {
+        let container = Box::new(Orientation::Horizontal, 2);
+        container.set_widget_name("pinned-apps");
+        container.add_css_class("modules");
+
+        let apps: Vec<PinnedApp> = config
+            .apps
+            .iter()
+            .filter_map(|id| match resolve_app(id) {
+                Some(app) => Some(PinnedApp { app, button: Button::new() }),
+                None => {
+                    LOG.error(&format!("No .desktop file for pinned app '{}'", id));
+                    None
+                }
+            })
+            .collect();
+
+        let widget = Self {
+            container,
+            config: Rc::new(config),
+            apps: Rc::new(apps),
+            windows: Rc::new(RefCell::new(HashMap::new())),
+            active: Rc::new(RefCell::new(None)),
+        };
+
+        for (index, pinned) in widget.apps.iter().enumerate() {
+            widget.setup_button(index, pinned);
+            widget.container.append(&pinned.button);
+        }
+        widget.container.set_visible(!widget.apps.is_empty());
+
+        // running windows come from the compositor
+        if _is_hyprland_session() && !widget.apps.is_empty() {
+            widget.update_windows();
+            widget.start_event_listener();
+        }
+        widget
+    }
+
+    pub fn widget(&self) -> &Box {
+        &self.container
+    }
+
+    fn setup_button(&self, index: usize, pinned: &PinnedApp) {
+        let button = &pinned.button;
+        button.add_css_class("pinned-app");
+
+        let icon = match pinned.app.icon() {
+            Some(gicon) => Image::from_gicon(&gicon),
+            None => Image::from_icon_name("application-x-executable"),
+        };
+        icon.set_pixel_size(self.config.icon_size);
+        button.set_child(Some(&icon));
+
+        if self.config.tooltip {
+            button.set_tooltip_text(Some(&pinned.app.display_name()));
+        }
+
+        // Left click focuses an open window or launches
+        let widget = self.clone();
+        button.connect_clicked(move |button| {
+            let pinned = &widget.apps[index];
+            if widget.config.focus_running && widget.focus_window(&pinned.app) {
+                return;
+            }
+            launch(&pinned.app, button);
+        });
+
+        // Middle click always starts a new instance
+        let middle_click = GestureClick::new();
+        middle_click.set_button(gdk::BUTTON_MIDDLE);
+        let widget = self.clone();
+        middle_click.connect_released(move |gesture, _, _, _| {
+            if let Some(button) = gesture.widget() {
+                launch(&widget.apps[index].app, &button);
+            }
+        });
+        button.add_controller(middle_click);
+    }
+
+    // false when the app has no window
+    fn focus_window(&self, app: &DesktopAppInfo) -> bool {
+        let Some(id) = app.id() else {
+            return false;
+        };
+        let windows = self.windows.borrow();
+        let active = self.active.borrow();
+        let Some(window) = windows.get(id.as_str()).and_then(|windows| window_to_focus(windows, active.as_ref())) else {
+            return false;
+        };
+
+        if let Err(e) = Dispatch::call(DispatchType::FocusWindow(WindowIdentifier::Address(window.address.clone()))) {
+            LOG.error(&format!("Failed to focus {}: {}", id, e));
+        }
+        true
+    }
+
+    fn start_event_listener(&self) {
+        let (tx, rx) = async_channel::unbounded();
+
+        spawn_event_listener("pinned_apps", tx, (), |event_listener, tx| {
+            let tx_clone = tx.clone();
+            event_listener.add_window_opened_handler(move |_| send_refresh(&tx_clone));
+
+            let tx_clone = tx.clone();
+            event_listener.add_window_closed_handler(move |_| send_refresh(&tx_clone));
+
+            let tx_clone = tx.clone();
+            event_listener.add_active_window_changed_handler(move |_| send_refresh(&tx_clone));
+        });
+
+        let widget = self.clone();
+        attach_events(&self.container, rx, |_| {}, move || widget.update_windows());
+    }
+
+    fn update_windows(&self) {
+        let clients = match Clients::get() {
+            Ok(clients) => clients.to_vec(),
+            Err(e) => {
+                LOG.error(&format!("Failed to get windows: {}", e));
+                return;
+            }
+        };
+        let active = Client::get_active().ok().flatten().map(|client| client.address);
+
+        let mut windows = self.windows.borrow_mut();
+        windows.clear();
+        for pinned in self.apps.iter() {
+            let Some(id) = pinned.app.id() else {
+                continue;
+            };
+            let app_windows = app_windows(&clients, &id);
+
+            let focused = app_windows.iter().any(|window| Some(&window.address) == active.as_ref());
+            set_class(&pinned.button, "running", !app_windows.is_empty());
+            set_class(&pinned.button, "active", focused);
+            windows.insert(id.to_string(), app_windows);
+        }
+        *self.active.borrow_mut() = active;
+    }
+}
+
+fn set_class(widget: &impl IsA<gtk::Widget>, class: &str, enabled: bool) {
+    if enabled {
+        widget.add_css_class(class);
+    } else {
+        widget.remove_css_class(class);
+    }
+}
+
+fn launch(app: &DesktopAppInfo, widget: &impl IsA<gtk::Widget>) {
+    let context = widget.display().app_launch_context();
+    if let Err(e) = app.launch(&[], Some(&context)) {
+        LOG.error(&format!("Failed to launch {}: {}", app.display_name(), e));
+    }
+}
+
+fn send_refresh(tx: &async_channel::Sender<()>) {
+    if let Err(e) = tx.send_blocking(()) {
+        LOG.error(&format!("Failed to send pinned apps event: {}", e));
+    }
+}
@@ -0,0 +1,47 @@
+use gio::DesktopAppInfo;
+use hyprland::data::Client;
+use hyprland::shared::Address;
+
+use crate::ui::modules::hyprland::window::window_helper::get_desktop_id;
+
+// "firefox" and "firefox.desktop" both name firefox.desktop
+pub fn normalize_id(id: &str) -> String {
+    let id = id.trim();
+    if id.ends_with(".desktop") {
+        id.to_string()
+    } else {
+        format!("{}.desktop", id)
+    }
+}
+
+pub fn resolve_app(id: &str) -> Option<DesktopAppInfo> {
+    DesktopAppInfo::new(&normalize_id(id))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppWindow {
+    pub address: Address,
+    pub focus_history_id: i8, // 0 is the focused window
+}
+
+// Windows of an app in a stable order
+pub fn app_windows(clients: &[Client], desktop_id: &str) -> Vec<AppWindow> {
+    let mut windows: Vec<AppWindow> = clients
+        .iter()
+        .filter(|client| get_desktop_id(&client.class).is_some_and(|id| id == desktop_id))
+        .map(|client| AppWindow {
+            address: client.address.clone(),
+            focus_history_id: client.focus_history_id,
+        })
+        .collect();
+    windows.sort_by_key(|window| window.address.to_string());
+    windows
+}
+
+// Window to focus on click: the next one when the app is focused, else the most recent one
+pub fn window_to_focus<'a>(windows: &'a [AppWindow], active: Option<&Address>) -> Option<&'a AppWindow> {
+    match windows.iter().position(|window| Some(&window.address) == active) {
+        Some(index) => windows.get((index + 1) % windows.len()),
+        None => windows.iter().min_by_key(|window| window.focus_history_id),
+    }
+}
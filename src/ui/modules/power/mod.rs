@@ -0,0 +1,2 @@
+pub mod power;
+pub mod power_helper;
\ No newline at end of file
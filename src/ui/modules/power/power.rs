@@ -0,0 +1,167 @@
+// Power menu module
+use gtk::{glib, prelude::*};
+use gtk::{Align, Box as GtkBox, Button, Image, Label, Orientation, Popover, Stack};
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::config::config::PowerConfig;
+use crate::ui::bar::set_keyboard_focus;
+use crate::ui::modules::power::power_helper::{run_action, PowerAction};
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("power",LogLevel::Debug);
+}
+
+pub struct PowerMenu {
+    button: Button,
+}
+
+impl PowerMenu {
+    pub fn new(config: PowerConfig) -> Self {
+        let button = Button::with_label(&config.icon);
+        button.set_widget_name("power");
+        button.add_css_class("modules");
+        button.set_tooltip_text(Some("Power"));
+
+        let actions: Vec<PowerAction> = config
+            .actions
+            .iter()
+            .filter_map(|name| {
+                let action = PowerAction::from_name(name);
+                if action.is_none() {
+                    LOG.error(&format!("Unknown power action '{}'", name));
+                }
+                action
+            })
+            .collect();
+
+        let popover = create_popover(&button, &actions, Rc::new(config));
+        button.connect_clicked(move |_| {
+            if popover.is_visible() {
+                popover.popdown();
+            } else {
+                popover.popup();
+            }
+        });
+
+        Self { button }
+    }
+
+    pub fn widget(&self) -> &Button {
+        &self.button
+    }
+}
+
+// Action list, and a confirmation page for actions listed in `confirm`
+// Arrow keys and Tab move between buttons, Enter runs, Escape closes
+fn create_popover(parent: &Button, actions: &[PowerAction], config: Rc<PowerConfig>) -> Popover {
+    let popover = Popover::new();
+    popover.set_parent(parent);
+    popover.set_autohide(true);
+
+    let stack = Stack::new();
+    stack.set_widget_name("power-menu");
+
+    let list = GtkBox::new(Orientation::Vertical, 2);
+    stack.add_named(&list, Some("actions"));
+
+    let confirm_page = GtkBox::new(Orientation::Vertical, 8);
+    let question = Label::new(None);
+    question.add_css_class("heading");
+    confirm_page.append(&question);
+    let confirm_buttons = GtkBox::new(Orientation::Horizontal, 6);
+    confirm_buttons.set_halign(Align::Center);
+    let cancel = Button::with_label("Cancel");
+    let confirm = Button::new();
+    confirm.add_css_class("destructive-action");
+    confirm_buttons.append(&cancel);
+    confirm_buttons.append(&confirm);
+    confirm_page.append(&confirm_buttons);
+    stack.add_named(&confirm_page, Some("confirm"));
+    popover.set_child(Some(&stack));
+
+    let pending: Rc<Cell<Option<PowerAction>>> = Rc::new(Cell::new(None));
+
+    for action in actions.iter().copied() {
+        let content = GtkBox::new(Orientation::Horizontal, 8);
+        content.append(&Image::from_icon_name(action.icon_name()));
+        content.append(&Label::new(Some(action.label())));
+
+        let action_button = Button::new();
+        action_button.set_child(Some(&content));
+        action_button.add_css_class("flat");
+        action_button.add_css_class("power-action");
+
+        let stack = stack.clone();
+        let question = question.clone();
+        let confirm = confirm.clone();
+        let cancel = cancel.clone();
+        let pending = pending.clone();
+        let popover = popover.clone();
+        let parent = parent.clone();
+        let config = config.clone();
+        action_button.connect_clicked(move |_| {
+            if config.confirm.iter().any(|name| name == action.name()) {
+                pending.set(Some(action));
+                question.set_text(&format!("{} now?", action.label()));
+                confirm.set_label(action.label());
+                stack.set_visible_child_name("confirm");
+                // safer default for an accidental Enter
+                cancel.grab_focus();
+                return;
+            }
+            popover.popdown();
+            execute(action, &config, &parent);
+        });
+        list.append(&action_button);
+    }
+
+    let stack_clone = stack.clone();
+    let pending_clone = pending.clone();
+    cancel.connect_clicked(move |_| {
+        pending_clone.set(None);
+        stack_clone.set_visible_child_name("actions");
+    });
+
+    let popover_clone = popover.clone();
+    let parent_clone = parent.clone();
+    confirm.connect_clicked(move |_| {
+        if let Some(action) = pending.take() {
+            popover_clone.popdown();
+            execute(action, &config, &parent_clone);
+        }
+    });
+
+    let list_clone = list.clone();
+    popover.connect_show(move |popover| {
+        set_keyboard_focus(popover, true);
+        stack.set_visible_child_name("actions");
+        if let Some(first) = list_clone.first_child() {
+            first.grab_focus();
+        }
+    });
+    popover.connect_closed(|popover| set_keyboard_focus(popover, false));
+
+    popover
+}
+
+fn execute(action: PowerAction, config: &PowerConfig, button: &Button) {
+    LOG.debug(&format!("Power action: {}", action.name()));
+    let command = config.commands.get(action.name()).cloned();
+    let button = button.clone();
+    glib::spawn_future_local(async move {
+        match run_action(action, command.as_deref()).await {
+            Ok(()) => {
+                button.remove_css_class("error");
+                button.set_tooltip_text(Some("Power"));
+            }
+            Err(e) => {
+                LOG.error(&e);
+                button.add_css_class("error");
+                button.set_tooltip_text(Some(&e));
+            }
+        }
+    });
+}
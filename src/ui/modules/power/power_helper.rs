@@ -0,0 +1,137 @@
+// Power actions through logind or configured commands
+use std::env;
+use tokio::process::Command;
+use zbus::zvariant::OwnedObjectPath;
+use zbus::Connection;
+
+const LOGIN1: &str = "org.freedesktop.login1";
+const LOGIN1_PATH: &str = "/org/freedesktop/login1";
+const LOGIN1_MANAGER: &str = "org.freedesktop.login1.Manager";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PowerAction {
+    Lock,
+    Logout,
+    Suspend,
+    Hibernate,
+    Reboot,
+    Shutdown,
+}
+
+impl PowerAction {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lock" => Some(PowerAction::Lock),
+            "logout" => Some(PowerAction::Logout),
+            "suspend" => Some(PowerAction::Suspend),
+            "hibernate" => Some(PowerAction::Hibernate),
+            "reboot" => Some(PowerAction::Reboot),
+            "shutdown" => Some(PowerAction::Shutdown),
+            _ => None,
+        }
+    }
+
+    // Key in the config
+    pub fn name(&self) -> &'static str {
+        match self {
+            PowerAction::Lock => "lock",
+            PowerAction::Logout => "logout",
+            PowerAction::Suspend => "suspend",
+            PowerAction::Hibernate => "hibernate",
+            PowerAction::Reboot => "reboot",
+            PowerAction::Shutdown => "shutdown",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerAction::Lock => "Lock",
+            PowerAction::Logout => "Log Out",
+            PowerAction::Suspend => "Suspend",
+            PowerAction::Hibernate => "Hibernate",
+            PowerAction::Reboot => "Reboot",
+            PowerAction::Shutdown => "Shut Down",
+        }
+    }
+
+    pub fn icon_name(&self) -> &'static str {
+        match self {
+            PowerAction::Lock => "system-lock-screen-symbolic",
+            PowerAction::Logout => "system-log-out-symbolic",
+            PowerAction::Suspend => "media-playback-pause-symbolic",
+            PowerAction::Hibernate => "document-save-symbolic",
+            PowerAction::Reboot => "system-reboot-symbolic",
+            PowerAction::Shutdown => "system-shutdown-symbolic",
+        }
+    }
+}
+
+// Configured command if there is one, else the logind call
+pub async fn run_action(action: PowerAction, command: Option<&str>) -> Result<(), String> {
+    match command.filter(|command| !command.trim().is_empty()) {
+        Some(command) => Command::new("sh")
+            .args(["-c", command])
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("Failed to run '{}': {}", command, e)),
+        None => logind_action(action).await.map_err(|e| format!("logind {}: {}", action.name(), e)),
+    }
+}
+
+async fn logind_action(action: PowerAction) -> zbus::Result<()> {
+    let connection = Connection::system().await?;
+    // true lets polkit ask for a password when needed
+    let interactive = true;
+
+    match action {
+        // only our own session, LockSessions locks every session and needs admin rights
+        PowerAction::Lock => {
+            let id = session_id(&connection).await?;
+            call(&connection, "LockSession", &(id,)).await
+        }
+        PowerAction::Logout => {
+            let id = session_id(&connection).await?;
+            call(&connection, "TerminateSession", &(id,)).await
+        }
+        PowerAction::Suspend => call(&connection, "Suspend", &(interactive,)).await,
+        PowerAction::Hibernate => call(&connection, "Hibernate", &(interactive,)).await,
+        PowerAction::Reboot => call(&connection, "Reboot", &(interactive,)).await,
+        PowerAction::Shutdown => call(&connection, "PowerOff", &(interactive,)).await,
+    }
+}
+
+async fn call<B>(connection: &Connection, method: &str, body: &B) -> zbus::Result<()>
+where
+    B: serde::ser::Serialize + zbus::zvariant::DynamicType,
+{
+    connection
+        .call_method(Some(LOGIN1), LOGIN1_PATH, Some(LOGIN1_MANAGER), method, body)
+        .await?;
+    Ok(())
+}
+
+// Session of the bar, from the environment or asked from logind by pid
+async fn session_id(connection: &Connection) -> zbus::Result<String> {
+    if let Ok(id) = env::var("XDG_SESSION_ID")
+        && !id.is_empty()
+    {
+        return Ok(id);
+    }
+
+    let reply = connection
+        .call_method(Some(LOGIN1), LOGIN1_PATH, Some(LOGIN1_MANAGER), "GetSessionByPID", &(std::process::id(),))
+        .await?;
+    let path: OwnedObjectPath = reply.body().deserialize()?;
+
+    let reply = connection
+        .call_method(
+            Some(LOGIN1),
+            path.as_str(),
+            Some("org.freedesktop.DBus.Properties"),
+            "Get",
+            &("org.freedesktop.login1.Session", "Id"),
+        )
+        .await?;
+    let id: zbus::zvariant::OwnedValue = reply.body().deserialize()?;
+    String::try_from(id).map_err(zbus::Error::from)
+}
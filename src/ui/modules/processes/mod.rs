@@ -0,0 +1,2 @@
+pub mod processes;
+pub mod processes_helper;
\ No newline at end of file
@@ -0,0 +1,167 @@
+// Top processes popover shown when clicking cpu or memory
+use gtk::{gdk, glib, prelude::*};
+use gtk::{Align, Box, Button, GestureClick, Label, ListBox, Orientation, Popover, SelectionMode};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+use sysinfo::Pid;
+
+use crate::config::config::TopProcessesConfig;
+use crate::ui::modules::processes::processes_helper::{ProcessInfo, ProcessMonitor, ProcessSort};
+use crate::utils::format::format_size;
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("processes",LogLevel::Debug);
+}
+
+// Open the popover on left click of `widget`
+pub fn attach_process_popover(widget: &impl IsA<gtk::Widget>, sort: ProcessSort, config: TopProcessesConfig) {
+    if !config.enabled {
+        return;
+    }
+
+    let popover = Popover::new();
+    popover.set_parent(widget);
+    popover.set_autohide(true);
+
+    let content = Box::new(Orientation::Vertical, 6);
+    content.set_widget_name("top-processes");
+
+    let title = match sort {
+        ProcessSort::Cpu => "Top processes by CPU",
+        ProcessSort::Memory => "Top processes by memory",
+    };
+    let header = Label::new(Some(title));
+    header.add_css_class("heading");
+    header.set_halign(Align::Start);
+    content.append(&header);
+
+    let list = ListBox::new();
+    list.set_selection_mode(SelectionMode::Single);
+    content.append(&list);
+
+    let monitor = Rc::new(RefCell::new(ProcessMonitor::new()));
+    let selected: Rc<Cell<Option<Pid>>> = Rc::new(Cell::new(None));
+    let shown: Rc<RefCell<Vec<Pid>>> = Rc::new(RefCell::new(Vec::new())); // pid of each row
+
+    if config.allow_kill {
+        let kill_button = Button::with_label("End process");
+        kill_button.set_halign(Align::End);
+        kill_button.set_sensitive(false);
+
+        let kill_clone = kill_button.clone();
+        let selected_clone = selected.clone();
+        let shown_clone = shown.clone();
+        list.connect_row_selected(move |_, row| {
+            // rows are rebuilt on refresh, keep the selection by pid
+            if let Some(row) = row {
+                selected_clone.set(shown_clone.borrow().get(row.index() as usize).copied());
+            }
+            kill_clone.set_sensitive(row.is_some());
+        });
+
+        let monitor_clone = monitor.clone();
+        let selected_clone = selected.clone();
+        kill_button.connect_clicked(move |_| {
+            if let Some(pid) = selected_clone.take() {
+                LOG.debug(&format!("Sending SIGTERM to {}", pid));
+                if !monitor_clone.borrow().terminate(pid) {
+                    LOG.error(&format!("Failed to terminate process {}", pid));
+                }
+            }
+        });
+        content.append(&kill_button);
+    }
+    popover.set_child(Some(&content));
+
+    // periodic refresh, removed when the popover closes so reopening does not stack timers
+    let refresh_source: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+    {
+        let refresh_source = refresh_source.clone();
+        popover.connect_closed(move |_| {
+            if let Some(source) = refresh_source.borrow_mut().take() {
+                source.remove();
+            }
+        });
+    }
+
+    let gesture = GestureClick::new();
+    gesture.set_button(gdk::BUTTON_PRIMARY);
+    gesture.connect_released(move |gesture, _, x, y| {
+        // clicks inside the popover bubble up to the module too
+        if !gesture.widget().is_some_and(|w| w.contains(x, y)) {
+            return;
+        }
+        if popover.is_visible() {
+            popover.popdown();
+            return;
+        }
+
+        // first sample has no cpu usage, take it before showing the list
+        monitor.borrow_mut().top(sort, config.count);
+        selected.set(None);
+        popover.popup();
+
+        let refresh = {
+            let list = list.clone();
+            let monitor = monitor.clone();
+            let selected = selected.clone();
+            let shown = shown.clone();
+            let count = config.count;
+            move || {
+                let processes = monitor.borrow_mut().top(sort, count);
+                *shown.borrow_mut() = processes.iter().map(|p| p.pid).collect();
+                fill_list(&list, &processes, sort, selected.get());
+            }
+        };
+        // short first delay so cpu usage is measured over a meaningful span
+        let first_refresh = refresh.clone();
+        glib::timeout_add_local_once(Duration::from_millis(250), first_refresh);
+
+        let source = glib::timeout_add_local(Duration::from_secs(config.interval.max(1) as u64), move || {
+            refresh();
+            glib::ControlFlow::Continue
+        });
+        if let Some(previous) = refresh_source.borrow_mut().replace(source) {
+            previous.remove();
+        }
+    });
+    widget.add_controller(gesture);
+}
+
+fn fill_list(list: &ListBox, processes: &[ProcessInfo], sort: ProcessSort, selected: Option<Pid>) {
+    list.remove_all();
+
+    for (index, process) in processes.iter().enumerate() {
+        let row_box = Box::new(Orientation::Horizontal, 12);
+
+        let name = Label::new(Some(&process.name));
+        name.set_hexpand(true);
+        name.set_halign(Align::Start);
+        name.set_max_width_chars(24);
+        name.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        row_box.append(&name);
+
+        let pid = Label::new(Some(&process.pid.to_string()));
+        pid.add_css_class("dim-label");
+        row_box.append(&pid);
+
+        let value = match sort {
+            ProcessSort::Cpu => format!("{:.1}%", process.cpu),
+            ProcessSort::Memory => format_size(process.memory),
+        };
+        let value = Label::new(Some(&value));
+        value.set_width_chars(8);
+        value.set_xalign(1.0);
+        row_box.append(&value);
+
+        list.append(&row_box);
+        if selected == Some(process.pid)
+            && let Some(row) = list.row_at_index(index as i32)
+        {
+            list.select_row(Some(&row));
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProcessSort {
+    Cpu,    // by cpu usage, 100% is one full core
+    Memory, // by resident memory
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    pub pid: Pid,
+    pub name: String,
+    pub cpu: f32,
+    pub memory: u64, // RSS in bytes
+}
+
+// Keeps the previous sample as cpu usage is measured between refreshes
+pub struct ProcessMonitor {
+    system: System,
+}
+
+impl ProcessMonitor {
+    pub fn new() -> Self {
+        Self { system: System::new() }
+    }
+
+    pub fn top(&mut self, sort: ProcessSort, count: usize) -> Vec<ProcessInfo> {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+
+        let mut processes: Vec<ProcessInfo> = self
+            .system
+            .processes()
+            .values()
+            // skip kernel threads
+            .filter(|p| p.thread_kind().is_none() && p.memory() > 0)
+            .map(|p| ProcessInfo {
+                pid: p.pid(),
+                name: p.name().to_string_lossy().into_owned(),
+                cpu: p.cpu_usage(),
+                memory: p.memory(),
+            })
+            .collect();
+
+        match sort {
+            ProcessSort::Cpu => processes.sort_by(|a, b| b.cpu.total_cmp(&a.cpu)),
+            ProcessSort::Memory => processes.sort_by_key(|p| std::cmp::Reverse(p.memory)),
+        }
+        processes.truncate(count);
+        processes
+    }
+
+    // Ask a process to quit, false if it is gone or not ours
+    pub fn terminate(&self, pid: Pid) -> bool {
+        self.system
+            .process(pid)
+            .and_then(|p| p.kill_with(Signal::Term))
+            .unwrap_or(false)
+    }
+}
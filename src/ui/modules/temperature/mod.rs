@@ -0,0 +1,2 @@
+pub mod temperature;
+pub mod temperature_helper;
\ No newline at end of file
@@ -0,0 +1,64 @@
+/* TEMPERATURE MODULE */
+
+use gtk::{Label, glib, prelude::*};
+
+use crate::config::config::TemperatureConfig;
+use crate::system::hwmon::{find_sensor, SensorKind};
+use crate::ui::modules::temperature::temperature_helper::{
+    format_temperature_text, format_temperature_tooltip, is_critical,
+};
+
+pub struct Temperature {
+    label: Label,
+    config: TemperatureConfig,
+}
+
+impl Temperature {
+    pub fn new(config: TemperatureConfig) -> Self {
+        let label = Label::new(None);
+        label.set_widget_name("temperature");
+        label.add_css_class("modules");
+
+        Self { label, config }
+    }
+
+    pub fn widget(&self) -> &Label {
+        &self.label
+    }
+
+    pub fn start_updates(self) {
+        let label = self.label.clone();
+        let config = self.config;
+        let interval = config.interval.max(1);
+
+        let Some(sensor) = find_sensor(SensorKind::Temperature, &config.chip, &config.label) else {
+            label.set_text("No sensor");
+            return;
+        };
+
+        let update = move || {
+            let Some(celsius) = sensor.read() else {
+                label.set_text("N/A");
+                return;
+            };
+
+            let critical = is_critical(celsius, sensor, &config);
+            label.set_text(&format_temperature_text(celsius, sensor, critical, &config));
+            if critical {
+                label.add_css_class("critical");
+            } else {
+                label.remove_css_class("critical");
+            }
+
+            if config.tooltip {
+                label.set_tooltip_markup(Some(&format_temperature_tooltip(&config)));
+            }
+        };
+
+        update();
+        glib::timeout_add_seconds_local(interval, move || {
+            update();
+            glib::ControlFlow::Continue
+        });
+    }
+}
@@ -0,0 +1,68 @@
+use crate::config::config::TemperatureConfig;
+use crate::system::hwmon::{sensors, Sensor, SensorKind};
+
+pub fn to_unit(celsius: f64, config: &TemperatureConfig) -> f64 {
+    if config.fahrenheit {
+        celsius * 9.0 / 5.0 + 32.0
+    } else {
+        celsius
+    }
+}
+
+pub fn unit(config: &TemperatureConfig) -> &'static str {
+    if config.fahrenheit { "°F" } else { "°C" }
+}
+
+// Map temperature to a nerd font thermometer
+pub fn get_temperature_icon(celsius: f64, critical: bool) -> &'static str {
+    match celsius {
+        _ if critical => "\u{f2c7}", // full
+        t if t >= 70.0 => "\u{f2c8}",
+        t if t >= 50.0 => "\u{f2c9}",
+        t if t >= 30.0 => "\u{f2ca}",
+        _ => "\u{f2cb}", // empty
+    }
+}
+
+// Threshold from config or the limit of the sensor
+pub fn is_critical(celsius: f64, sensor: &Sensor, config: &TemperatureConfig) -> bool {
+    let threshold = if config.critical_threshold > 0.0 {
+        Some(config.critical_threshold)
+    } else {
+        sensor.critical
+    };
+    threshold.is_some_and(|t| celsius >= t)
+}
+
+// Format main widget text
+pub fn format_temperature_text(celsius: f64, sensor: &Sensor, critical: bool, config: &TemperatureConfig) -> String {
+    config
+        .format
+        .replace("{icon}", get_temperature_icon(celsius, critical))
+        .replace("{temp}", &format!("{:.0}", to_unit(celsius, config)))
+        .replace("{unit}", unit(config))
+        .replace("{chip}", &sensor.chip)
+        .replace("{label}", &sensor.label)
+        .trim()
+        .to_string()
+}
+
+// Format tooltip, one line per temperature sensor
+pub fn format_temperature_tooltip(config: &TemperatureConfig) -> String {
+    let lines: Vec<String> = sensors()
+        .iter()
+        .filter(|s| s.kind == SensorKind::Temperature)
+        .filter_map(|sensor| {
+            let celsius = sensor.read()?;
+            Some(format!(
+                "{} {}: {:.0}{}",
+                sensor.chip,
+                sensor.label,
+                to_unit(celsius, config),
+                unit(config)
+            ))
+        })
+        .collect();
+
+    format!("<b>Temperature</b>\n{}", glib::markup_escape_text(&lines.join("\n")))
+}
@@ -61,6 +61,42 @@ pub fn create_modules_page() -> Box {
         list.append(&row);
     }
 
+    // Temperature
+    {
+        let row = switch_row("Temperature", config.modules.temperature);
+        let cfg = Rc::clone(&config_state);
+        attach_switch_handler(&row, move |state| {
+            let mut c = cfg.borrow_mut();
+            c.modules.temperature = state;
+            LOG.debug(&format!("Temperature module set to: {}", state));
+        });
+        list.append(&row);
+    }
+
+    // Fans
+    {
+        let row = switch_row("Fan Speed", config.modules.fan);
+        let cfg = Rc::clone(&config_state);
+        attach_switch_handler(&row, move |state| {
+            let mut c = cfg.borrow_mut();
+            c.modules.fan = state;
+            LOG.debug(&format!("Fan module set to: {}", state));
+        });
+        list.append(&row);
+    }
+
+    // Disk
+    {
+        let row = switch_row("Disk Usage", config.modules.disk);
+        let cfg = Rc::clone(&config_state);
+        attach_switch_handler(&row, move |state| {
+            let mut c = cfg.borrow_mut();
+            c.modules.disk = state;
+            LOG.debug(&format!("Disk module set to: {}", state));
+        });
+        list.append(&row);
+    }
+
     // Window Title, only on hyprland for now
     {
         let row = switch_row("Window Title", config.modules.window_title);
@@ -85,6 +121,90 @@ pub fn create_modules_page() -> Box {
         list.append(&row);
     }
 
+    // Taskbar, only on hyprland for now
+    {
+        let row = switch_row("Taskbar", config.modules.taskbar);
+        let cfg = Rc::clone(&config_state);
+        attach_switch_handler(&row, move |state| {
+            let mut c = cfg.borrow_mut();
+            c.modules.taskbar = state;
+            LOG.debug(&format!("Taskbar module set to: {}", state));
+        });
+        list.append(&row);
+    }
+
+    // Submap, only on hyprland
+    {
+        let row = switch_row("Submap", config.modules.submap);
+        let cfg = Rc::clone(&config_state);
+        attach_switch_handler(&row, move |state| {
+            let mut c = cfg.borrow_mut();
+            c.modules.submap = state;
+            LOG.debug(&format!("Submap module set to: {}", state));
+        });
+        list.append(&row);
+    }
+
+    // Keyboard layout, only on hyprland for now
+    {
+        let row = switch_row("Keyboard Layout", config.modules.keyboard_layout);
+        let cfg = Rc::clone(&config_state);
+        attach_switch_handler(&row, move |state| {
+            let mut c = cfg.borrow_mut();
+            c.modules.keyboard_layout = state;
+            LOG.debug(&format!("Keyboard layout module set to: {}", state));
+        });
+        list.append(&row);
+    }
+
+    // Pinned apps
+    {
+        let row = switch_row("Pinned Apps", config.modules.pinned_apps);
+        let cfg = Rc::clone(&config_state);
+        attach_switch_handler(&row, move |state| {
+            let mut c = cfg.borrow_mut();
+            c.modules.pinned_apps = state;
+            LOG.debug(&format!("Pinned apps module set to: {}", state));
+        });
+        list.append(&row);
+    }
+
+    // Power menu
+    {
+        let row = switch_row("Power Menu", config.modules.power);
+        let cfg = Rc::clone(&config_state);
+        attach_switch_handler(&row, move |state| {
+            let mut c = cfg.borrow_mut();
+            c.modules.power = state;
+            LOG.debug(&format!("Power module set to: {}", state));
+        });
+        list.append(&row);
+    }
+
+    // Idle inhibitor
+    {
+        let row = switch_row("Idle Inhibitor", config.modules.idle_inhibitor);
+        let cfg = Rc::clone(&config_state);
+        attach_switch_handler(&row, move |state| {
+            let mut c = cfg.borrow_mut();
+            c.modules.idle_inhibitor = state;
+            LOG.debug(&format!("Idle inhibitor module set to: {}", state));
+        });
+        list.append(&row);
+    }
+
+    // Backlight
+    {
+        let row = switch_row("Backlight", config.modules.backlight);
+        let cfg = Rc::clone(&config_state);
+        attach_switch_handler(&row, move |state| {
+            let mut c = cfg.borrow_mut();
+            c.modules.backlight = state;
+            LOG.debug(&format!("Backlight module set to: {}", state));
+        });
+        list.append(&row);
+    }
+
     // Battery
     {
         let row = switch_row("Battery Info", config.modules.battery);
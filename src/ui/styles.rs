@@ -36,6 +36,33 @@ button{
 #launcher:active {
   border: 0px;
 }
+#launcher.error {
+  color: @error;
+}
+
+#pinned-apps .pinned-app {
+  padding: 2px 6px;
+  border-radius: 8px;
+  border-bottom: 2px solid transparent;
+}
+
+#pinned-apps .pinned-app.running {
+  border-bottom-color: alpha(@on_surface, 0.4);
+}
+
+#pinned-apps .pinned-app.active {
+  border-bottom-color: #a6e3a1;
+}
+
+#app-menu .app-menu-categories {
+  background: transparent;
+  min-width: 120px;
+}
+
+#app-menu .app-menu-apps row {
+  padding: 4px 6px;
+  border-radius: 6px;
+}
 
 .modules {
   padding: 0 12px;
@@ -68,13 +95,47 @@ button{
   box-shadow: 0 2px 4px rgba(0, 0, 0, 0.2);
 }
 
+#workspaces #ws-button.empty {
+  opacity: 0.5;
+}
+
+/* shown on this monitor but focus is on another one */
+#workspaces #ws-button.visible {
+  border: 1px solid alpha(currentColor, 0.5);
+}
+
 #workspaces #ws-button.active {
   box-shadow: 0 2px 4px rgba(0, 0, 0, 0.2);
   border: 2px solid;
 }
 
-/* #workspaces button.urgent {
-} */
+#workspaces #ws-button.urgent {
+  background-color: @error;
+  color: @on_error;
+}
+
+#special-workspaces #ws-button.special {
+  font-style: italic;
+}
+
+#taskbar .task-button {
+  padding: 0 6px;
+  border-radius: 8px;
+  transition: all 200ms ease;
+}
+
+#taskbar .task-button.active {
+  background: rgba(137, 180, 250, 0.3);
+}
+
+#taskbar .task-count {
+  font-size: 10px;
+}
+
+#submap {
+  font-weight: bold;
+  color: #f9e2af;
+}
 
 
 #clock,
@@ -95,6 +156,52 @@ margin: 0px 0px 0px 12px;
   padding: 8px;
 }
 
+#clock-calendar calendar {
+  border: none;
+}
+
+#clock-calendar calendar > grid > label.today {
+  background-color: #a6e3a1;
+  color: #1e1e2e;
+  border-radius: 6px;
+}
+
+#clock-calendar calendar > grid > label.week-number {
+  opacity: 0.5;
+}
+
+#calendar-events .calendar-event {
+  font-size: 12px;
+}
+
+#clock-timer {
+  padding: 8px 8px 8px 0px;
+}
+
+.running > #clock-timer {
+  color: #f9e2af;
+}
+
+#clock-timers .timer-row.running .timer-time {
+  color: #a6e3a1;
+}
+
+.alarm > #clock {
+  background-color: @error;
+  color: #1e1e2e;
+  border-radius: 8px;
+}
+
+#clock-alarms .alarm-day {
+  min-width: 20px;
+  padding: 2px 4px;
+}
+
+#clock-timers .timer-time {
+  font-weight: bold;
+  min-width: 90px;
+}
+
 #volume {
   font-weight: bold;
   padding: 6px;
@@ -140,12 +247,60 @@ margin: 0px 0px 0px 12px;
   padding: 6px;
   transition: all 200ms;
 }
-/* #memory.medium {
-  color: yellow;
+#memory.medium {
+  color: #f9e2af;
 }
 #memory.high {
-  color: red;
-} */
+  color: #f38ba8;
+}
+
+#temperature {
+  padding: 6px;
+}
+
+#temperature.critical {
+  color: @error;
+}
+
+#disk {
+  padding: 6px;
+}
+
+#disk .warning {
+  color: #f9e2af;
+}
+
+#disk .critical {
+  color: @error;
+}
+
+#fan {
+  padding: 6px;
+}
+
+#fan.stopped {
+  opacity: 0.6;
+}
+
+#idle-inhibitor {
+  padding: 6px;
+}
+
+#idle-inhibitor.activated {
+  color: #f9e2af;
+}
+
+#power {
+  padding: 6px;
+}
+
+#power.error {
+  color: @error;
+}
+
+#power-menu .power-action {
+  padding: 6px 12px;
+}
 
 #backlight {
   background-color: @surface_container_high;
@@ -179,6 +334,32 @@ margin: 0px 0px 0px 12px;
   border-radius: 3px;
 }
 
+#cpu-bars #cpu-bar {
+  min-width: 4px;
+  min-height: 16px;
+}
+
+#cpu-bars #cpu-bar trough {
+  min-width: 4px;
+}
+
+#cpu-bars #cpu-bar progress {
+  min-width: 4px;
+  background: #a6e3a1;
+}
+
+#cpu-bars #cpu-bar.medium progress {
+  background: #f9e2af;
+}
+
+#cpu-bars #cpu-bar.high progress {
+  background: #f38ba8;
+}
+
+#cpu-graph {
+  min-height: 16px;
+}
+
 #memory-bar trough {
   background: rgba(88, 91, 112, 0.5);
   border-radius: 3px;
@@ -0,0 +1,24 @@
+// Shared formatting of sizes for memory, disk and network modules
+
+// Human readable size with binary units
+// 512 -> "512 B", 1536 -> "1.5 KB", 3221225472 -> "3.0 GB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KB", "MB", "GB", "TB", "PB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+// Transfer rate, eg: "1.5 MB/s"
+pub fn format_rate(bytes_per_second: u64) -> String {
+    format!("{}/s", format_size(bytes_per_second))
+}
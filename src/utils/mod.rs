@@ -1 +1,5 @@
-pub mod logger;
\ No newline at end of file
+pub mod logger;
+pub mod format;
+pub mod notify;
+pub mod paths;
+pub mod state;
\ No newline at end of file
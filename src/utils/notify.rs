@@ -0,0 +1,39 @@
+// Desktop notifications and sounds
+use gio::prelude::*;
+use tokio::process::Command;
+
+use crate::utils::logger::{LogLevel, Logger};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref LOG: Logger = Logger::new("notify",LogLevel::Debug);
+}
+
+// Notifications with the same id replace each other
+pub fn send_notification(id: &str, title: &str, body: &str) {
+    let Some(app) = gio::Application::default() else {
+        LOG.error("No application to send notifications from");
+        return;
+    };
+
+    let notification = gio::Notification::new(title);
+    notification.set_body(Some(body));
+    notification.set_priority(gio::NotificationPriority::High);
+    app.send_notification(Some(id), &notification);
+}
+
+// Run a shell command eg: a sound player, empty commands are ignored
+pub fn run_command(command: &str) {
+    if command.trim().is_empty() {
+        return;
+    }
+    let command = command.to_string();
+    glib::spawn_future_local(async move {
+        match Command::new("sh").args(["-c", &command]).spawn() {
+            Ok(mut child) => {
+                let _ = child.wait().await;
+            }
+            Err(e) => LOG.error(&format!("Failed to run '{}': {}", command, e)),
+        }
+    });
+}
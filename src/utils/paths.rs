@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+// Expand "~/" to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => glib::home_dir().join(rest),
+        None if path == "~" => glib::home_dir(),
+        None => PathBuf::from(path),
+    }
+}
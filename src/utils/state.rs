@@ -0,0 +1,36 @@
+// State files kept across restarts in $XDG_STATE_HOME/better-bar
+use serde::{de::DeserializeOwned, Serialize};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+pub fn state_dir() -> PathBuf {
+    let base = match env::var("XDG_STATE_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => {
+            let home = env::var("HOME").expect("Could not find home directory");
+            PathBuf::from(home).join(".local").join("state")
+        }
+    };
+    base.join("better-bar")
+}
+
+// Missing or unreadable files give the default state
+pub fn load_state<T: DeserializeOwned + Default>(name: &str) -> T {
+    fs::read_to_string(state_dir().join(name))
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_state<T: Serialize>(name: &str, state: &T) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = state_dir();
+    fs::create_dir_all(&dir)?;
+
+    // write then rename so a crash never leaves a half written file
+    let path = dir.join(name);
+    let tmp = dir.join(format!("{}.tmp", name));
+    fs::write(&tmp, toml::to_string_pretty(state)?)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}